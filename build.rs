@@ -1,37 +1,112 @@
-use std::fs;
+use std::{collections::BTreeMap, env, fs, path::{Path, PathBuf}, process};
 
-// This file is very messy and does not follow best practice (modifies src/).
-// I don't care. It works.
+// Finds every day in src/years and writes a registry of them into OUT_DIR, along with a test for
+// every sample answer in input/. Day modules are pulled in with #[path], so src/ is never written to.
 
 fn main() {
-	let mut year_list = String::new();
+	let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+	let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+	let mut modules = String::new();
+	let mut registry = String::new();
+	let mut errors = vec![];
+	// Which parts have been written, by (year, day).
+	let mut written = BTreeMap::new();
+
 	println!("cargo::rerun-if-changed=src/years");
-	for entry in fs::read_dir("src/years").unwrap() {
-		let Ok(entry) = entry else { continue };
-		if entry.file_type().unwrap().is_dir() {
-			let year_name = entry.file_name();
-			let year_name = year_name.to_string_lossy();
-			println!("cargo::rerun-if-changed=src/years/{year_name}");
-			year_list.push_str(&format!("#[cfg(feature = \"{0}\")] #[allow(dead_code)] mod {0};\n#[cfg(all(feature = \"{0}\", not(feature = \"default\")))] use {0} as year;\n", year_name));
-
-			let mut day_list = vec![];
-			for day_entry in fs::read_dir(entry.path()).unwrap() {
-				let Ok(day_entry) = day_entry else { continue };
-				let name = day_entry.file_name();
-				let name = name.to_string_lossy();
-
-				if name.starts_with("day") && name.ends_with(".rs") {
-					let number: u8 = name[3..name.len()-3].parse().unwrap();
-					day_list.push(number);
-					println!("cargo::rerun-if-changed=src/years/{year_name}/day{number}.rs");
-				}
+	let mut entries = fs::read_dir("src/years").unwrap().filter_map(Result::ok).collect::<Vec<_>>();
+	entries.sort_by_key(|e| e.file_name());
+	for entry in entries {
+		if !entry.file_type().unwrap().is_dir() {
+			continue;
+		}
+		let year_name = entry.file_name().to_string_lossy().into_owned();
+		let Some(year) = year_name.strip_prefix("year").and_then(|y| y.parse::<usize>().ok()).filter(|&y| y >= 2015) else {
+			errors.push(format!("src/years/{year_name} should be named yearYYYY, e.g. year2024"));
+			continue;
+		};
+		println!("cargo::rerun-if-changed=src/years/{year_name}");
+
+		let mut day_list = vec![];
+		for day_entry in fs::read_dir(entry.path()).unwrap().filter_map(Result::ok) {
+			let name = day_entry.file_name().to_string_lossy().into_owned();
+			let Some(number) = name.strip_prefix("day").and_then(|n| n.strip_suffix(".rs")) else { continue };
+			let Some(number) = number.parse::<usize>().ok().filter(|n| (1..=25).contains(n)) else {
+				errors.push(format!("src/years/{year_name}/{name} should be named dayN.rs, with N from 1 to 25"));
+				continue;
+			};
+			if let Some((_, other)) = day_list.iter().find(|(n, _)| *n == number) {
+				errors.push(format!("src/years/{year_name}/{name} and {other} are both day {number}"));
+				continue;
 			}
+			day_list.push((number, name));
+		}
+		day_list.sort();
+
+		modules.push_str(&format!("#[allow(dead_code)]\nmod {year_name} {{\n"));
+		for (n, name) in &day_list {
+			let path = root.join("src/years").join(&year_name).join(name);
+			println!("cargo::rerun-if-changed={}", path.display());
+			modules.push_str(&format!("\t#[path = {:?}] pub mod day{n};\n", path.display().to_string()));
+
+			// A part is only registered once its function exists, so half-finished days still build.
+			let source = fs::read_to_string(&path).unwrap();
+			let (part1, part2) = (source.contains("pub fn part1("), source.contains("pub fn part2("));
+			written.insert((year, *n), [part1, part2]);
+			let solver = |part: usize, exists: bool| if exists { format!("Some({year_name}::day{n}::part{part})") } else { String::from("None") };
+			let params = if source.contains("pub const PARAMS:") { format!("{year_name}::day{n}::PARAMS") } else { String::from("&[]") };
+			registry.push_str(&format!("\tDay {{ year: {year}, day: {n}, part1: {}, part2: {}, params: {params} }},\n", solver(1, part1), solver(2, part2)));
+		}
+		modules.push_str("}\n");
+	}
+
+	if !errors.is_empty() {
+		for error in errors {
+			eprintln!("error: {error}");
+		}
+		process::exit(1);
+	}
+
+	write_if_changed(out_dir.join("registry.rs"), format!("{modules}\npub const DAYS: &[Day] = &[\n{registry}];\n"));
+	write_if_changed(out_dir.join("sample_tests.rs"), sample_tests(&written));
+}
+
+// Rewriting an identical file would still bump its mtime and force a recompile.
+fn write_if_changed(path: impl AsRef<Path>, contents: String) {
+	if fs::read_to_string(&path).ok().as_ref() != Some(&contents) {
+		fs::write(path, contents).unwrap();
+	}
+}
 
-			day_list.sort();
-			let day_list_str = day_list.into_iter().map(|n| format!("#[cfg(feature = \"day{n}\")] mod day{n};\n#[cfg(all(feature = \"day{n}\", not(feature = \"default\")))] use day{n} as day;")).collect::<Vec<String>>().join("\n");
+// One test per part of every sample that has an answer in its front matter, for days where that part exists.
+fn sample_tests(written: &BTreeMap<(usize, usize), [bool; 2]>) -> String {
+	let mut tests = String::new();
+	let Ok(years) = fs::read_dir("input") else { return tests };
+	// Only samples live in input/ (the cache is elsewhere), so this doesn't fire on every download.
+	println!("cargo::rerun-if-changed=input");
 
-			fs::write(format!("src/years/{year_name}/generated_list.rs"), day_list_str).unwrap();
+	let mut samples = vec![];
+	for year_entry in years.filter_map(Result::ok) {
+		let Ok(year) = year_entry.file_name().to_string_lossy().parse::<usize>() else { continue };
+		for entry in fs::read_dir(year_entry.path()).unwrap().filter_map(Result::ok) {
+			let name = entry.file_name().to_string_lossy().into_owned();
+			let Some((day, number)) = name.strip_prefix("day").and_then(|n| n.split_once(".sample")) else { continue };
+			let number = if number.is_empty() { Ok(1) } else { number.parse::<usize>() };
+			let (Ok(day), Ok(number)) = (day.parse::<usize>(), number) else { continue };
+			samples.push((year, day, number, fs::read_to_string(entry.path()).unwrap()));
+		}
+	}
+	samples.sort();
+
+	for (year, day, number, text) in samples {
+		let Some(parts) = written.get(&(year, day)) else { continue };
+		// Only needs to know which answers are present; the test itself reads the sample properly.
+		let header = text.trim_start().strip_prefix("---").and_then(|rest| rest.split_once("\n---")).map_or("", |(header, _)| header);
+		for (part, name) in [(1, "One"), (2, "Two")] {
+			let has_answer = header.lines().any(|line| line.split_once(':').is_some_and(|(key, _)| key.trim() == format!("part{part}")));
+			if has_answer && parts[part - 1] {
+				tests.push_str(&format!("#[test] fn year{year}_day{day}_sample{number}_part{part}() {{ check_sample({year}, {day}, {number}, Part::{name}) }}\n"));
+			}
 		}
 	}
-	fs::write("src/years/generated_list.rs", year_list).unwrap();
+	tests
 }
@@ -1,5 +1,26 @@
 use crate::lib::prelude::*;
 
-pub fn main(input: String) -> Result<String> {
-	Ok(format!(""))
+fn parse(input: &str) -> Vec<&str> {
+	input.lines().collect()
+}
+
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let _lines = parsing(|| parse(&input));
+	bail!("not written yet")
+}
+
+pub fn part2(input: String, _: &Context) -> Result<String> {
+	let _lines = parsing(|| parse(&input));
+	bail!("not written yet")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Whole samples are checked against their front matter already; this is for the pieces.
+	#[test]
+	fn parses_lines() {
+		assert_eq!(parse("a\nb"), ["a", "b"]);
+	}
 }
@@ -0,0 +1,88 @@
+//! Known-correct answers and checking solutions against them
+
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use crate::lib::{ config::config, prelude::* };
+use crate::runner::{ self, Job, Outcome, Status };
+use crate::store::PartStore;
+
+///Answers that have been accepted, stored per year in `answers.toml` in the cache directory.
+pub type Answers = PartStore<String>;
+
+pub fn load_answers(year: usize) -> Result<Answers> {
+	PartStore::load(config().year_dir(year).join("answers.toml"))
+}
+
+///Saves an answer that the site has accepted.
+pub fn record(year: usize, day: usize, part: Part, answer: String) -> Result<()> {
+	let mut answers = load_answers(year)?;
+	answers.set(day, part, answer);
+	answers.save()
+}
+
+///How a part's answer compares to the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq, EnumIs)]
+pub enum Verdict {
+	Pass,
+	Fail { expected: String },
+	///No answer has been recorded for this part yet.
+	Unknown,
+	///The solution did not produce an answer at all.
+	Broken,
+}
+
+impl Verdict {
+	pub fn check(status: &Status, expected: Option<&String>) -> Verdict {
+		match (status, expected) {
+			(Status::Solved(answer), Some(expected)) if answer == expected => Verdict::Pass,
+			(Status::Solved(_), Some(expected)) => Verdict::Fail { expected: expected.clone() },
+			(Status::Solved(_), None) => Verdict::Unknown,
+			_ => Verdict::Broken,
+		}
+	}
+}
+
+///Runs every part and compares it to the recorded answers, printing a pass/fail/unknown table. With
+///`record`, answers for parts that have none recorded yet are saved. Returns whether nothing failed.
+pub fn verify(jobs: &[Job], record: bool) -> Result<bool> {
+	let mut stores: BTreeMap<usize, Answers> = BTreeMap::new();
+	let mut all_passed = true;
+
+	println!("{:<4}  {:>3}  {:>4}  {:>10}  {:<7}  Answer", "Year", "Day", "Part", "Time", "Verdict");
+	for Job { day, parts, input, ctx, .. } in jobs {
+		let answers = match stores.entry(day.year) {
+			Entry::Occupied(entry) => entry.into_mut(),
+			Entry::Vacant(entry) => entry.insert(load_answers(day.year)?),
+		};
+
+		for &part in parts {
+			let outcome = runner::run(day, part, input.clone(), ctx);
+			let verdict = Verdict::check(&outcome.status, answers.get(day.day, part));
+			let Outcome { year, day: day_number, status, .. } = &outcome;
+
+			let detail = match &verdict {
+				Verdict::Fail { expected } => format!("{status} (expected {expected})"),
+				_ => status.to_string(),
+			};
+			let label = match &verdict {
+				Verdict::Pass => "pass",
+				Verdict::Fail { .. } => "FAIL",
+				Verdict::Unknown => "unknown",
+				Verdict::Broken => "BROKEN",
+			};
+			println!("{year:<4}  {day_number:>3}  {part:>4}  {:>10}  {label:<7}  {detail}", format!("{:.2?}", outcome.total()));
+
+			all_passed &= verdict.is_pass() || verdict.is_unknown();
+			if record && verdict.is_unknown() && let Status::Solved(answer) = outcome.status {
+				answers.set(day.day, part, answer);
+			}
+		}
+	}
+
+	if record {
+		for answers in stores.values() {
+			answers.save()?;
+		}
+	}
+	Ok(all_passed)
+}
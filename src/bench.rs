@@ -0,0 +1,96 @@
+//! Repeated runs, timing statistics and saved baselines
+
+use std::{collections::{btree_map::Entry, BTreeMap}, time::Duration};
+
+use crate::lib::{ config::config, prelude::* };
+use crate::runner::{ self, Job };
+use crate::store::PartStore;
+
+///How much slower than its baseline a part may get before it is reported as a regression.
+const REGRESSION_THRESHOLD: f64 = 0.10;
+
+///Timing statistics over several runs of one part.
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+	pub min: Duration,
+	pub median: Duration,
+	pub mean: Duration,
+}
+
+impl Stats {
+	///Computes statistics from a list of run times, or nothing if it's empty.
+	pub fn new(mut times: Vec<Duration>) -> Option<Stats> {
+		times.sort();
+		let min = *times.first()?;
+		let mean = times.iter().sum::<Duration>() / times.len() as u32;
+		Some(Stats { min, median: times[times.len() / 2], mean })
+	}
+}
+
+///Median run times, in nanoseconds, that later benchmarks are compared against.
+type Baselines = PartStore<u64>;
+
+fn load_baselines(year: usize) -> Result<Baselines> {
+	PartStore::load(config().year_dir(year).join("bench.toml"))
+}
+
+///Describes how a median compares to its baseline, flagging regressions.
+fn compare(median: Duration, baseline: Option<Duration>) -> String {
+	let Some(baseline) = baseline else { return String::from("new baseline") };
+	let change = median.as_secs_f64() / baseline.as_secs_f64() - 1.0;
+	let flag = if change > REGRESSION_THRESHOLD { "  REGRESSION" } else { "" };
+	format!("{:+.1}% vs {baseline:.2?}{flag}", change * 100.0)
+}
+
+///Runs each part of each day `iterations` times and prints timing statistics. Parts without a
+///baseline get one; existing baselines are only replaced when `save` is set. Jobs on samples or
+///other inputs are never compared against baselines, since those are for the downloaded inputs.
+pub fn bench(jobs: &[Job], iterations: usize, save: bool) -> Result<()> {
+	let mut baselines: BTreeMap<usize, Baselines> = BTreeMap::new();
+
+	println!("{:<4}  {:>3}  {:>4}  {:>10}  {:>10}  {:>10}  Baseline", "Year", "Day", "Part", "Min", "Median", "Mean");
+
+	for &Job { day, ref parts, ref input, ref ctx, baseline: compare_baseline } in jobs {
+		let year_baselines = match baselines.entry(day.year) {
+			Entry::Occupied(entry) => entry.into_mut(),
+			Entry::Vacant(entry) => entry.insert(load_baselines(day.year)?),
+		};
+
+		for &part in parts {
+			let mut times = Vec::with_capacity(iterations);
+			let mut failure = None;
+			for _ in 0..iterations {
+				let outcome = runner::run(day, part, input.clone(), ctx);
+				if !outcome.status.is_solved() {
+					failure = Some(outcome.status);
+					break;
+				}
+				times.push(outcome.total());
+			}
+
+			if let Some(status) = failure {
+				println!("{:<4}  {:>3}  {:>4}  {status}", day.year, day.day, part);
+				continue;
+			}
+
+			let Some(stats) = Stats::new(times) else { continue };
+			let baseline = if !compare_baseline { None } else { year_baselines.get(day.day, part).copied().map(Duration::from_nanos) };
+			println!(
+				"{:<4}  {:>3}  {:>4}  {:>10}  {:>10}  {:>10}  {}",
+				day.year, day.day, part,
+				format!("{:.2?}", stats.min), format!("{:.2?}", stats.median), format!("{:.2?}", stats.mean),
+				if !compare_baseline { String::new() } else { compare(stats.median, baseline) }
+			);
+			if compare_baseline && (baseline.is_none() || save) {
+				year_baselines.set(day.day, part, stats.median.as_nanos() as u64);
+			}
+		}
+	}
+
+	if jobs.iter().all(|job| job.baseline) {
+		for year_baselines in baselines.values() {
+			year_baselines.save()?;
+		}
+	}
+	Ok(())
+}
@@ -0,0 +1,141 @@
+//! Pulling samples out of puzzle pages
+
+use crate::lib::{ html, input::{ self, Sample }, prelude::* };
+
+///A `<pre><code>` block from a puzzle page.
+pub struct Block {
+	///Which part's description it's in.
+	pub part: Part,
+	pub text: String,
+}
+
+///The parts of a puzzle page that samples can be made from.
+pub struct Puzzle {
+	pub blocks: Vec<Block>,
+	///The last highlighted `<code><em>` in each part's description, which is usually the answer for
+	///the sample.
+	pub part1: Option<String>,
+	pub part2: Option<String>,
+}
+
+impl Puzzle {
+	pub fn parse(page: &str) -> Puzzle {
+		let mut puzzle = Puzzle { blocks: vec![], part1: None, part2: None };
+		for (article, part) in html::elements(page, "article").into_iter().zip([Part::One, Part::Two]) {
+			for pre in html::elements(article, "pre") {
+				let code = html::elements(pre, "code").into_iter().next().unwrap_or(pre);
+				puzzle.blocks.push(Block { part, text: html::text(code) });
+			}
+			let answer = html::elements(article, "code").into_iter().rev()
+				.find_map(|code| code.trim().strip_prefix("<em>")?.strip_suffix("</em>"))
+				.map(html::text);
+			match part {
+				Part::One => puzzle.part1 = answer,
+				Part::Two => puzzle.part2 = answer,
+			}
+		}
+		puzzle
+	}
+
+	///Guesses which answers go with a block: the answer at the end of its own part, and part 2's
+	///too if part 2 has no blocks of its own and so reuses part 1's example.
+	pub fn answers(&self, block: &Block) -> (Option<String>, Option<String>) {
+		match block.part {
+			Part::One if self.blocks.iter().any(|b| b.part.is_two()) => (self.part1.clone(), None),
+			Part::One => (self.part1.clone(), self.part2.clone()),
+			Part::Two => (None, self.part2.clone()),
+		}
+	}
+}
+
+///Lists the blocks on a page, or saves the chosen ones (numbered from 1) as new samples.
+pub fn extract(year: usize, day: usize, page: &str, chosen: &[usize], with_answers: bool) -> Result<()> {
+	let puzzle = Puzzle::parse(page);
+	if puzzle.blocks.is_empty() {
+		bail!("No <pre><code> blocks found. Is this a puzzle page?");
+	}
+
+	if chosen.is_empty() {
+		for (i, block) in puzzle.blocks.iter().enumerate() {
+			let lines: Vec<&str> = block.text.lines().collect();
+			println!("Block {} (part {}, {} lines):", i + 1, block.part, lines.len());
+			for line in lines.iter().take(6) {
+				println!("    {line}");
+			}
+			if lines.len() > 6 {
+				println!("    ...");
+			}
+		}
+		for (part, answer) in [(Part::One, &puzzle.part1), (Part::Two, &puzzle.part2)] {
+			if let Some(answer) = answer {
+				println!("Part {part} answer: {answer}");
+			}
+		}
+		println!("Save blocks with --block N, adding --answers to record the answers above in them.");
+		return Ok(());
+	}
+
+	if let Some(n) = chosen.iter().find(|&&n| n == 0 || n > puzzle.blocks.len()) {
+		bail!("There is no block {n}; the page has {}", puzzle.blocks.len());
+	}
+	for &n in chosen {
+		let block = &puzzle.blocks[n - 1];
+		let mut sample = Sample { input: block.text.trim().to_string(), ..Default::default() };
+		if with_answers {
+			(sample.part1, sample.part2) = puzzle.answers(block);
+		}
+		println!("Saved block {n} to {}", input::add_sample(year, day, sample)?);
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	///A cut-down puzzle page with both parts unlocked.
+	const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>Here, the <code>total</code> is <code><em>11</em></code>.</p>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>For the same list, the score is <code><em>31</em></code>.</p>
+</article>
+</main>"#;
+
+	#[test]
+	fn parses_blocks_and_answers() {
+		let puzzle = Puzzle::parse(PAGE);
+		assert_eq!(puzzle.blocks.len(), 1);
+		assert_eq!(puzzle.blocks[0].part, Part::One);
+		assert_eq!(puzzle.blocks[0].text, "3   4\n4   3\n");
+		assert_eq!(puzzle.part1.as_deref(), Some("11"));
+		assert_eq!(puzzle.part2.as_deref(), Some("31"));
+	}
+
+	#[test]
+	fn part_two_reuses_part_one_block() {
+		let puzzle = Puzzle::parse(PAGE);
+		assert_eq!(puzzle.answers(&puzzle.blocks[0]), (Some(String::from("11")), Some(String::from("31"))));
+	}
+
+	#[test]
+	fn part_two_has_its_own_block() {
+		let page = PAGE.replace("score is", "<pre><code>1 2\n</code></pre> score is");
+		let puzzle = Puzzle::parse(&page);
+		assert_eq!(puzzle.blocks.len(), 2);
+		assert_eq!(puzzle.blocks[1].part, Part::Two);
+		assert_eq!(puzzle.answers(&puzzle.blocks[0]), (Some(String::from("11")), None));
+		assert_eq!(puzzle.answers(&puzzle.blocks[1]), (None, Some(String::from("31"))));
+	}
+
+	#[test]
+	fn only_part_one_unlocked() {
+		let page = PAGE.split("<article class=\"day-desc\"><h2 id=\"part2\">").next().unwrap();
+		let puzzle = Puzzle::parse(page);
+		assert_eq!(puzzle.part2, None);
+		assert_eq!(puzzle.answers(&puzzle.blocks[0]), (Some(String::from("11")), None));
+	}
+}
@@ -0,0 +1,113 @@
+//! Every answer that has been submitted, and what the site said about it
+
+use serde::{ Deserialize, Serialize };
+
+use crate::lib::{ config::config, prelude::*, submit::Response };
+use crate::store::PartStore;
+
+///The site's verdict on a guess. Replies that say nothing about the answer itself, like being rate
+///limited, are not recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "kebab-case")]
+pub enum Feedback {
+	#[strum(serialize = "right")]
+	Correct,
+	#[strum(serialize = "wrong")]
+	Wrong,
+	#[strum(serialize = "too high")]
+	TooHigh,
+	#[strum(serialize = "too low")]
+	TooLow,
+}
+
+impl Feedback {
+	pub fn from_response(response: &Response) -> Option<Feedback> {
+		match response {
+			Response::Correct => Some(Feedback::Correct),
+			Response::Wrong => Some(Feedback::Wrong),
+			Response::TooHigh => Some(Feedback::TooHigh),
+			Response::TooLow => Some(Feedback::TooLow),
+			_ => None,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Guess {
+	pub answer: String,
+	pub feedback: Feedback,
+}
+
+///Submitted guesses, oldest first, stored per year in `history.toml` in the cache directory.
+pub type History = PartStore<Vec<Guess>>;
+
+pub fn load_history(year: usize) -> Result<History> {
+	PartStore::load(config().year_dir(year).join("history.toml"))
+}
+
+///Checks an answer against earlier guesses, explaining why it can't be right if it has already been
+///rejected or falls outside the bounds set by "too high" and "too low" replies.
+pub fn check(guesses: &[Guess], answer: &str) -> Result<()> {
+	let number = answer.parse::<i128>().ok();
+	for Guess { answer: guess, feedback } in guesses {
+		if let Feedback::Correct = feedback {
+			bail!("This part was already solved with {guess}");
+		}
+		if guess == answer {
+			bail!("{answer} was already submitted and was {feedback}");
+		}
+		let (Some(number), Ok(guess)) = (number, guess.parse::<i128>()) else { continue };
+		match feedback {
+			Feedback::TooHigh if number >= guess => bail!("{answer} can't be right: {guess} was already too high"),
+			Feedback::TooLow if number <= guess => bail!("{answer} can't be right: {guess} was already too low"),
+			_ => {},
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn guess(answer: &str, feedback: Feedback) -> Guess {
+		Guess { answer: answer.to_string(), feedback }
+	}
+
+	#[test]
+	fn repeated_wrong_guess() {
+		let guesses = [guess("abc", Feedback::Wrong)];
+		assert!(check(&guesses, "abc").is_err());
+		assert!(check(&guesses, "abd").is_ok());
+	}
+
+	#[test]
+	fn too_high_bound() {
+		let guesses = [guess("100", Feedback::TooHigh)];
+		assert!(check(&guesses, "100").is_err());
+		assert!(check(&guesses, "150").is_err());
+		assert!(check(&guesses, "99").is_ok());
+	}
+
+	#[test]
+	fn too_low_bound() {
+		let guesses = [guess("100", Feedback::TooLow)];
+		assert!(check(&guesses, "100").is_err());
+		assert!(check(&guesses, "50").is_err());
+		assert!(check(&guesses, "101").is_ok());
+	}
+
+	#[test]
+	fn non_numeric_answer_skips_bounds() {
+		let guesses = [guess("100", Feedback::TooHigh), guess("10", Feedback::TooLow)];
+		assert!(check(&guesses, "1,2,3").is_ok());
+		assert!(check(&guesses, "500").is_err());
+	}
+
+	#[test]
+	fn already_solved() {
+		let guesses = [guess("42", Feedback::TooLow), guess("64", Feedback::Correct)];
+		assert!(check(&guesses, "64").is_err());
+		assert!(check(&guesses, "70").is_err());
+	}
+}
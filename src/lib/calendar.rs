@@ -0,0 +1,41 @@
+//! Which events and days exist
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+///The first year there was an event.
+pub const FIRST_YEAR: usize = 2015;
+
+///How many days an event has. Events were cut down to 12 days from 2025 on.
+pub fn days_in(year: usize) -> usize {
+	if year >= 2025 { 12 } else { 25 }
+}
+
+///The current year in UTC.
+fn this_year() -> usize {
+	let days = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / 86400;
+	civil_from_days(days as i64).0 as usize
+}
+
+///The newest event, which is this year's even before December so its days can be set up ahead of
+///time. Everything that goes through every year stops here.
+pub fn latest_year() -> usize {
+	this_year()
+}
+
+///Whether a year and day is a puzzle, whether or not it has unlocked yet.
+pub fn is_puzzle(year: usize, day: usize) -> bool {
+	(FIRST_YEAR..=latest_year()).contains(&year) && (1..=days_in(year)).contains(&day)
+}
+
+///Turns days since 1970-01-01 into a (year, month) pair. From Howard Hinnant's `civil_from_days`.
+fn civil_from_days(days: i64) -> (i64, u32) {
+	let z = days + 719468;
+	let era = z.div_euclid(146097);
+	let day_of_era = z.rem_euclid(146097);
+	let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let mp = (5 * day_of_year + 2) / 153;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+	let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+	(year, month)
+}
@@ -0,0 +1,73 @@
+//! Settings for talking to the site and keeping what comes back, read from the environment or `.env`
+
+use std::{path::PathBuf, sync::{OnceLock, RwLock}};
+
+///The real site, which asks that requests to it be spaced out.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+pub struct Config {
+	///`AOC_BASE_URL`: the site inputs are downloaded from and answers are submitted to. Point it at a
+	///local server for testing.
+	pub base_url: String,
+	///`AOC_CACHE_DIR`: where downloaded inputs and each year's answers, baselines and submission
+	///history are kept. Defaults to `cache`. Samples always stay in `input/`, since they're checked in,
+	///and nothing else goes there so that build.rs only reruns when a sample changes.
+	pub cache_dir: PathBuf,
+	///`AOC_USER_AGENT`: sent with every request. The site asks for it to say how to contact you, e.g.
+	///`github.com/you/advent by you@example.com`.
+	pub user_agent: String,
+	///`token_<name>`: the session cookies of other accounts, which each get their own inputs and
+	///answers. Sorted by name.
+	pub profiles: Vec<String>,
+}
+
+impl Config {
+	pub fn from_env() -> Config {
+		let var = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+		Config {
+			base_url: var("AOC_BASE_URL").map_or(String::from(DEFAULT_BASE_URL), |url| url.trim_end_matches('/').to_string()),
+			cache_dir: var("AOC_CACHE_DIR").map_or(PathBuf::from("cache"), PathBuf::from),
+			user_agent: var("AOC_USER_AGENT").unwrap_or(String::from(concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")))),
+			profiles: {
+				let mut names: Vec<String> = std::env::vars()
+					.filter_map(|(name, value)| Some(name.strip_prefix("token_")?.to_string()).filter(|n| !n.is_empty() && !value.trim().is_empty()))
+					.collect();
+				names.sort();
+				names
+			},
+		}
+	}
+
+	///The directory holding the current profile's cached files: the cache directory itself for the
+	///default profile, or `profiles/<name>` inside it.
+	pub fn profile_dir(&self) -> PathBuf {
+		match profile() {
+			Some(name) => self.cache_dir.join("profiles").join(name),
+			None => self.cache_dir.clone(),
+		}
+	}
+
+	///The directory holding one year's cached files.
+	pub fn year_dir(&self, year: usize) -> PathBuf {
+		self.profile_dir().join(year.to_string())
+	}
+}
+
+///Which account inputs are downloaded for and answers are kept for. `None` is the default one, using
+///`token`.
+static PROFILE: RwLock<Option<String>> = RwLock::new(None);
+
+///Switches to another profile, which should be one of [`Config::profiles`].
+pub fn set_profile(name: Option<&str>) {
+	*PROFILE.write().unwrap() = name.map(str::to_string);
+}
+
+pub fn profile() -> Option<String> {
+	PROFILE.read().unwrap().clone()
+}
+
+///Gets the config, reading it the first time. `.env` has to be loaded before then.
+pub fn config() -> &'static Config {
+	static CONFIG: OnceLock<Config> = OnceLock::new();
+	CONFIG.get_or_init(Config::from_env)
+}
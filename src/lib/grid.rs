@@ -3,14 +3,14 @@
 use std::{fmt::Display, marker::PhantomData};
 
 ///A neighborhood of 8 adjacent members, excluding the middle cell
-pub const MOORE_NEIGHBORHOOD: &'static [(isize, isize); 8] = &[
+pub const MOORE_NEIGHBORHOOD: &[(isize, isize); 8] = &[
 	(-1, -1), ( 0, -1), ( 1, -1),
 	(-1,  0), /*Self,*/ ( 1,  0),
 	(-1,  1), ( 0,  1), ( 1,  1),
 ];
 
 ///A neighborhood of 4 adjacent members, excluding the middle cell and diagonals
-pub const VON_NEUMANN_NEIGHBORHOOD: &'static [(isize, isize); 4] = &[
+pub const VON_NEUMANN_NEIGHBORHOOD: &[(isize, isize); 4] = &[
 	/*None,*/ ( 0, -1), /*None,*/
 	(-1,  0), /*Self,*/ ( 1,  0),
 	/*None,*/ ( 0,  1), /*None,*/
@@ -299,6 +299,12 @@ impl<T> ItemGrid<T> {
 
 }
 
+impl<T> Default for ItemGrid<T> {
+	fn default() -> ItemGrid<T> {
+		ItemGrid::new()
+	}
+}
+
 impl<T> Grid<T> for ItemGrid<T> {
 	fn get_checked(&self, x: usize, y: usize) -> Option<&T> {
 	    self.items.get(x)?.get(y)
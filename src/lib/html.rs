@@ -0,0 +1,289 @@
+//! Just enough HTML handling to get things out of puzzle pages, which are simple and well-formed
+
+///Gets what's inside every `<tag ...>...</tag>`, in order. Elements of the same tag nested in each
+///other aren't handled, which puzzle pages never do anyway.
+pub fn elements<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+	let (open, close) = (format!("<{tag}"), format!("</{tag}>"));
+	let mut found = vec![];
+	let mut rest = html;
+	while let Some(start) = rest.find(&open) {
+		let after = &rest[start + open.len()..];
+		// Skip tags that only start the same way, like <em> when looking for <e>.
+		if !after.starts_with(['>', ' ', '\n']) {
+			rest = after;
+			continue;
+		}
+		let Some(content_start) = after.find('>') else { break };
+		let content = &after[content_start + 1..];
+		let Some(end) = content.find(&close) else { break };
+		found.push(&content[..end]);
+		rest = &content[end + close.len()..];
+	}
+	found
+}
+
+///Replaces the entities puzzle pages use with the characters they stand for.
+pub fn decode_entities(text: &str) -> String {
+	let mut decoded = String::with_capacity(text.len());
+	let mut rest = text;
+	while let Some(start) = rest.find('&') {
+		decoded.push_str(&rest[..start]);
+		rest = &rest[start..];
+		let Some(end) = rest.find(';').filter(|&end| end <= 10) else {
+			decoded.push('&');
+			rest = &rest[1..];
+			continue;
+		};
+		let entity = &rest[1..end];
+		let character = match entity {
+			"lt" => Some('<'),
+			"gt" => Some('>'),
+			"amp" => Some('&'),
+			"quot" => Some('"'),
+			"apos" => Some('\''),
+			"nbsp" => Some(' '),
+			_ => entity.strip_prefix("#x").map(|hex| u32::from_str_radix(hex, 16))
+				.or_else(|| entity.strip_prefix('#').map(str::parse))
+				.and_then(Result::ok)
+				.and_then(char::from_u32),
+		};
+		match character {
+			Some(character) => {
+				decoded.push(character);
+				rest = &rest[end + 1..];
+			},
+			None => {
+				decoded.push('&');
+				rest = &rest[1..];
+			},
+		}
+	}
+	decoded.push_str(rest);
+	decoded
+}
+
+///Drops every tag, keeping only the text between them.
+pub fn strip_tags(html: &str) -> String {
+	let mut text = String::new();
+	let mut in_tag = false;
+	for c in html.chars() {
+		match c {
+			'<' => in_tag = true,
+			'>' => in_tag = false,
+			c if !in_tag => text.push(c),
+			_ => {},
+		}
+	}
+	text
+}
+
+///The text of some HTML, as it would read without any formatting.
+pub fn text(html: &str) -> String {
+	decode_entities(&strip_tags(html))
+}
+
+///Turns HTML into text for a terminal, wrapped to `width` columns. With `color`, emphasis, code and
+///links are styled with ANSI escapes.
+pub fn to_ansi(html: &str, width: usize, color: bool) -> String {
+	let mut renderer = Renderer { width, color, ..Default::default() };
+	let mut rest = html;
+	while !rest.is_empty() {
+		match rest.find('<') {
+			Some(0) => {
+				// A tag that's never closed is cut off, so there's nothing left worth showing.
+				let Some(end) = rest.find('>') else { break };
+				renderer.tag(&rest[1..end]);
+				rest = &rest[end + 1..];
+			},
+			Some(start) => {
+				renderer.text(&decode_entities(&rest[..start]));
+				rest = &rest[start..];
+			},
+			None => {
+				renderer.text(&decode_entities(rest));
+				rest = "";
+			},
+		}
+	}
+	renderer.out.truncate(renderer.out.trim_end().len());
+	renderer.styles.clear();
+	renderer.restyle = true;
+	renderer.write("\n");
+	renderer.out
+}
+
+#[derive(Default)]
+struct Renderer {
+	width: usize,
+	color: bool,
+	out: String,
+	column: usize,
+	///Whitespace was skipped since the last word, so the next one needs a space or a line break first.
+	space: bool,
+	///Inside a `<pre>`, where text is kept as it is.
+	pre: bool,
+	///ANSI codes for every style that's currently open, innermost last.
+	styles: Vec<&'static str>,
+	///The styles changed, so the escapes for them need writing before the next word.
+	restyle: bool,
+}
+
+impl Renderer {
+	fn tag(&mut self, tag: &str) {
+		let closing = tag.starts_with('/');
+		let name = tag.trim_start_matches('/').split([' ', '\n', '/']).next().unwrap_or("");
+		let style = match name {
+			"em" => Some("\x1b[1m"),
+			"code" if !self.pre => Some("\x1b[36m"),
+			"a" => Some("\x1b[4m"),
+			"h2" => Some("\x1b[1;32m"),
+			_ => None,
+		};
+		match (name, closing) {
+			("p" | "h2" | "article", _) => self.paragraph(),
+			("pre", false) => {
+				self.paragraph();
+				self.pre = true;
+			},
+			("pre", true) => {
+				self.pre = false;
+				self.paragraph();
+			},
+			("ul", _) => self.paragraph(),
+			("li", false) => {
+				self.line();
+				self.out.push_str("  - ");
+				self.column = 4;
+				self.space = false;
+			},
+			("br", _) => self.line(),
+			_ => {},
+		}
+		if let Some(style) = style {
+			self.restyle = true;
+			if closing {
+				self.styles.pop();
+				// Straight away, so the space before the next word isn't styled either.
+				self.write("");
+			} else {
+				self.styles.push(style);
+			}
+		}
+	}
+
+	///Writes a word, first switching to the current styles if they changed since the last one. Doing
+	///that here rather than at each tag keeps spaces and line breaks out of underlines.
+	fn write(&mut self, word: &str) {
+		if self.restyle && self.color {
+			self.out.push_str("\x1b[0m");
+			self.out.extend(self.styles.iter().copied());
+		}
+		self.restyle = false;
+		self.out.push_str(word);
+	}
+
+	///Ends the current line if anything is on it.
+	fn line(&mut self) {
+		if self.column > 0 {
+			self.out.push('\n');
+			self.column = 0;
+		}
+		self.space = false;
+	}
+
+	///Ends the current line and leaves a blank one, unless there already is one.
+	fn paragraph(&mut self) {
+		self.line();
+		if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+			self.out.push('\n');
+		}
+	}
+
+	fn text(&mut self, text: &str) {
+		if self.pre {
+			for (i, line) in text.split('\n').enumerate() {
+				if i > 0 {
+					self.line();
+				}
+				if !line.is_empty() {
+					if self.column == 0 {
+						self.out.push_str("    ");
+					}
+					self.write(line);
+					self.column += line.chars().count() + 4;
+				}
+			}
+			return;
+		}
+
+		for (i, word) in text.split(char::is_whitespace).enumerate() {
+			if i > 0 {
+				self.space = true;
+			}
+			if word.is_empty() {
+				continue;
+			}
+			let length = word.chars().count();
+			if self.space && self.column > 0 {
+				if self.column + 1 + length > self.width {
+					self.out.push('\n');
+					self.column = 0;
+				} else {
+					self.out.push(' ');
+					self.column += 1;
+				}
+			}
+			self.write(word);
+			self.column += length;
+			self.space = false;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finds_elements() {
+		let page = "<main><article class=\"day-desc\"><p>One</p></article>\n<em>x</em><article><p>Two</p></article></main>";
+		assert_eq!(elements(page, "article"), ["<p>One</p>", "<p>Two</p>"]);
+		assert_eq!(elements(page, "e"), Vec::<&str>::new());
+		assert_eq!(elements("<article>never closed", "article"), Vec::<&str>::new());
+	}
+
+	#[test]
+	fn decodes_entities() {
+		assert_eq!(decode_entities("a &lt;b&gt; &amp;&quot;&apos;&nbsp;"), "a <b> &\"' ");
+		assert_eq!(decode_entities("&#65;&#x42;"), "AB");
+		assert_eq!(decode_entities("fish & chips; &bogus; &"), "fish & chips; &bogus; &");
+	}
+
+	#[test]
+	fn strips_tags() {
+		assert_eq!(strip_tags("<p>Some <em>very</em> <a href=\"/\">odd</a> text</p>"), "Some very odd text");
+		assert_eq!(text("<code>a &lt; b</code>"), "a < b");
+	}
+
+	#[test]
+	fn wraps_text() {
+		let html = "<h2>--- Day 1 ---</h2><p>The quick <em>brown</em> fox jumps over the lazy dog.</p><ul><li>one</li><li>two</li></ul>";
+		assert_eq!(to_ansi(html, 20, false), "--- Day 1 ---\n\nThe quick brown fox\njumps over the lazy\ndog.\n\n  - one\n  - two\n");
+	}
+
+	#[test]
+	fn keeps_preformatted_text() {
+		let html = "<p>Like this:</p><pre><code>1  2\n3  4\n</code></pre><p>Done.</p>";
+		assert_eq!(to_ansi(html, 80, false), "Like this:\n\n    1  2\n    3  4\n\nDone.\n");
+	}
+
+	#[test]
+	fn colors_emphasis() {
+		assert_eq!(to_ansi("<p>a <em>b</em> c</p>", 80, true), "a \x1b[0m\x1b[1mb\x1b[0m c\x1b[0m\n");
+	}
+
+	#[test]
+	fn stops_at_an_unclosed_tag() {
+		assert_eq!(to_ansi("<p>Cut off here <a href=\"/2024", 80, false), "Cut off here\n");
+	}
+}
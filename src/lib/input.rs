@@ -1,35 +1,126 @@
-use std::fs;
+pub mod http;
 
-fn setup_input_dir(year: usize) -> std::io::Result<()> {
-	if !fs::exists("input")? {
-		fs::create_dir("input")?;
+use std::{collections::BTreeMap, fs, path::{ Path, PathBuf }};
+
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use super::{ calendar, config::config, html, puzzle::Part };
+
+///Everything that can go wrong getting an input or a sample.
+#[derive(Debug, Error)]
+pub enum InputError {
+	#[error("There is no event in {0}. They run every December starting from 2015.")]
+	InvalidYear(usize),
+	#[error("{year} day {day} is not a puzzle. Events have 25 days, or 12 from 2025 on.")]
+	InvalidDay { year: usize, day: usize },
+	#[error("No session token. Add `{0}=` followed by your adventofcode.com session cookie to .env.")]
+	MissingToken(String),
+	#[error("The session token was rejected, so it has probably expired. Log in again and copy the new session cookie into .env.")]
+	ExpiredSession,
+	#[error("{year} day {day} has not unlocked yet.")]
+	NotUnlocked { year: usize, day: usize },
+	#[error("The server replied with {0}.")]
+	Status(StatusCode),
+	#[error("{path} not found. Please paste sample data in.")]
+	MissingSample { path: String },
+	#[error("{path}: {reason}")]
+	FrontMatter { path: String, reason: String },
+	#[error("Could not read {path}")]
+	Unreadable { path: String, source: std::io::Error },
+	#[error("Request failed: {0}")]
+	Request(#[from] reqwest::Error),
+	#[error(transparent)]
+	Io(#[from] std::io::Error),
+}
+
+pub fn check_year(year: usize) -> Result<(), InputError> {
+	if !calendar::is_puzzle(year, 1) {
+		return Err(InputError::InvalidYear(year));
 	}
-	if !fs::exists(format!("input/{year}"))? {
-		fs::create_dir(format!("input/{year}"))?;
+	Ok(())
+}
+
+pub fn check_day(year: usize, day: usize) -> Result<(), InputError> {
+	check_year(year)?;
+	if !calendar::is_puzzle(year, day) {
+		return Err(InputError::InvalidDay { year, day });
 	}
 	Ok(())
 }
 
-pub fn input(year: usize, day: usize) -> Result<String, reqwest::Error> {
-	assert!(year >= 2015);
-	assert!(day >= 1 && day <= 25);
-	setup_input_dir(year).unwrap();
-	
-	match fs::read_to_string(format!("input/{year}/day{day}.input")) {
-		Ok(content) => return Ok(content),
-		Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
-		Err(e) => eprintln!("{e:?}"),
+fn setup_input_dir(year: usize) -> std::io::Result<()> {
+	fs::create_dir_all(config().year_dir(year))
+}
+
+///Where a day's downloaded input is kept.
+pub fn input_path(year: usize, day: usize) -> PathBuf {
+	config().year_dir(year).join(format!("day{day}.input"))
+}
+
+///Reads an input that has already been downloaded, without touching the network.
+pub fn cached_input(year: usize, day: usize) -> Option<String> {
+	match fs::read_to_string(input_path(year, day)) {
+		Ok(content) => Some(content),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+		Err(e) => {
+			eprintln!("{e:?}");
+			None
+		},
 	}
+}
+
+///Downloads the puzzle's page. It's cached once both parts have been solved, since it won't change
+///after that.
+pub fn puzzle_page(year: usize, day: usize) -> Result<String, InputError> {
+	fetch_puzzle_page(year, day, |page| page.contains("Both parts of this puzzle are complete"))
+}
+
+///Downloads the puzzle's page, leaving it to `done` whether the HTTP cache keeps it.
+fn fetch_puzzle_page(year: usize, day: usize, done: impl Fn(&str) -> bool) -> Result<String, InputError> {
+	check_day(year, day)?;
+	match http::get(&format!("/{year}/day/{day}"), done) {
+		Err(InputError::Status(StatusCode::NOT_FOUND)) => Err(InputError::NotUnlocked { year, day }),
+		page => page,
+	}
+}
+
+///Gets the puzzle's description, as the HTML of its `<article>`s, from `dayN.html` alongside the
+///input. The site is asked again each time until part 2 is in it, since it won't change after that.
+pub fn description(year: usize, day: usize) -> Result<String, InputError> {
+	check_day(year, day)?;
+	let path = config().year_dir(year).join(format!("day{day}.html"));
+	if let Ok(saved) = fs::read_to_string(&path) && html::elements(&saved, "article").len() >= 2 {
+		return Ok(saved);
+	}
+
+	// `dayN.html` is the only copy kept, rather than the whole page in the HTTP cache too.
+	let page = fetch_puzzle_page(year, day, |_| false)?;
+	let articles: String = html::elements(&page, "article").into_iter().map(|article| format!("<article>{article}</article>\n")).collect();
+	setup_input_dir(year)?;
+	fs::write(path, &articles)?;
+	Ok(articles)
+}
 
-	let client = reqwest::blocking::Client::new();
-	let r = client.request(reqwest::Method::GET, format!("https://adventofcode.com/{year}/day/{day}/input"))
-		.header("Cookie", format!("session={}", std::env::var("token").unwrap()))
-		.build()?;
+pub fn input(year: usize, day: usize) -> Result<String, InputError> {
+	check_day(year, day)?;
+	setup_input_dir(year)?;
+
+	if let Some(content) = cached_input(year, day) {
+		return Ok(content);
+	}
 
-	let response = client.execute(r)?.error_for_status()?;
-	let text = response.text()?.trim().to_string();
+	// Inputs never change, so they're kept from the first download, in `input_path` rather than the
+	// HTTP cache.
+	let text = match http::get(&format!("/{year}/day/{day}/input"), |_| false) {
+		Ok(text) => text.trim().to_string(),
+		// "Puzzle inputs differ by user. Please log in to get your puzzle input."
+		Err(InputError::Status(StatusCode::BAD_REQUEST)) => return Err(InputError::ExpiredSession),
+		Err(InputError::Status(StatusCode::NOT_FOUND)) => return Err(InputError::NotUnlocked { year, day }),
+		Err(e) => return Err(e),
+	};
 
-	match fs::write(format!("input/{year}/day{day}.input"), &text) {
+	match fs::write(input_path(year, day), &text) {
 		Ok(()) => {},
 		Err(e) => eprintln!("{e:?}"),
 	}
@@ -37,14 +128,153 @@ pub fn input(year: usize, day: usize) -> Result<String, reqwest::Error> {
 	Ok(text)
 }
 
-pub fn sample(year: usize, day: usize) -> Result<String, std::io::Error> {
-	assert!(year >= 2015);
-	assert!(day >= 1 && day <= 25);
-	setup_input_dir(year)?;
-	let path = format!("input/{year}/day{day}.sample{}", if cfg!(feature = "sample2") { "2" } else { "" });
+///Reads an input from anywhere, rather than the cache: a file, or stdin if `path` is `-`. Trailing
+///whitespace is dropped, like it is from downloaded inputs.
+pub fn read_input(path: &str) -> Result<String, InputError> {
+	let text = if path == "-" {
+		std::io::read_to_string(std::io::stdin())
+	} else {
+		fs::read_to_string(path)
+	}.map_err(|source| InputError::Unreadable { path: String::from(if path == "-" { "stdin" } else { path }), source })?;
+	Ok(text.trim_end().to_string())
+}
+
+///A pasted sample, along with whatever its front matter says about it.
+#[derive(Debug, Clone, Default)]
+pub struct Sample {
+	pub number: usize,
+	pub input: String,
+	///The answers the puzzle text gives for this sample, if they have been written down.
+	pub part1: Option<String>,
+	pub part2: Option<String>,
+	///Every other front matter key, e.g. a grid size that differs from the real input's.
+	pub params: BTreeMap<String, String>,
+}
+
+impl Sample {
+	///Splits off the optional front matter, which is `key: value` lines between two `---` lines at
+	///the top of the file:
+	///```text
+	///---
+	///part1: 11
+	///part2: 31
+	///---
+	///3   4
+	///```
+	pub fn parse(number: usize, text: &str) -> Result<Sample, String> {
+		let mut sample = Sample { number, ..Default::default() };
+		let body = match text.trim_start().strip_prefix("---") {
+			Some(rest) => {
+				let Some((header, body)) = rest.split_once("\n---") else { return Err(String::from("front matter is never closed with ---")) };
+				for line in header.lines().map(str::trim).filter(|l| !l.is_empty()) {
+					let Some((key, value)) = line.split_once(':') else { return Err(format!("expected `key: value` in front matter, got {line:?}")) };
+					let value = value.trim().to_string();
+					match key.trim() {
+						"part1" => sample.part1 = Some(value),
+						"part2" => sample.part2 = Some(value),
+						key => { sample.params.insert(key.to_string(), value); },
+					}
+				}
+				body
+			},
+			None => text,
+		};
+		sample.input = body.trim().to_string();
+		Ok(sample)
+	}
+
+	///Writes the sample back out in the format [`Sample::parse`] reads.
+	pub fn to_file(&self) -> String {
+		let mut header: Vec<String> = self.part1.iter().map(|answer| format!("part1: {answer}")).collect();
+		header.extend(self.part2.iter().map(|answer| format!("part2: {answer}")));
+		header.extend(self.params.iter().map(|(key, value)| format!("{key}: {value}")));
+		if header.is_empty() {
+			format!("{}\n", self.input)
+		} else {
+			format!("---\n{}\n---\n{}\n", header.join("\n"), self.input)
+		}
+	}
+
+	///Gets the recorded answer for a part.
+	pub fn expected(&self, part: Part) -> Option<&str> {
+		match part {
+			Part::One => self.part1.as_deref(),
+			Part::Two => self.part2.as_deref(),
+		}
+	}
+}
+
+///Where a sample is kept. Samples are checked in, so they're always in `input/` wherever the cache
+///is. Sample 1 is stored in `dayN.sample`, and every other sample `n` is stored in `dayN.sample<n>`.
+pub fn sample_path(year: usize, day: usize, number: usize) -> String {
+	format!("input/{year}/day{day}.sample{}", if number == 1 { String::new() } else { number.to_string() })
+}
+
+///Lists the numbers of a day's samples.
+pub fn samples(year: usize, day: usize) -> Vec<usize> {
+	let Ok(entries) = fs::read_dir(format!("input/{year}")) else { return vec![] };
+	let prefix = format!("day{day}.sample");
+	let mut numbers: Vec<usize> = entries.filter_map(Result::ok).filter_map(|entry| {
+		let number = entry.file_name().to_string_lossy().strip_prefix(&prefix)?.to_string();
+		if number.is_empty() { Some(1) } else { number.parse().ok() }
+	}).collect();
+	numbers.sort();
+	numbers
+}
+
+///Reads a pasted sample.
+pub fn sample(year: usize, day: usize, number: usize) -> Result<Sample, InputError> {
+	check_day(year, day)?;
+	let path = sample_path(year, day, number);
 	if !fs::exists(&path)? {
-		return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("{path} not found. Please paste sample data in.")));
+		return Err(InputError::MissingSample { path });
+	}
+
+	Sample::parse(number, &fs::read_to_string(&path)?).map_err(|reason| InputError::FrontMatter { path, reason })
+}
+
+///Saves a sample under the first number that isn't taken yet, returning where it went.
+pub fn add_sample(year: usize, day: usize, mut sample: Sample) -> Result<String, InputError> {
+	check_day(year, day)?;
+	fs::create_dir_all(format!("input/{year}"))?;
+	sample.number = (1..).find(|&n| !Path::new(&sample_path(year, day, n)).exists()).unwrap();
+	let path = sample_path(year, day, sample.number);
+	fs::write(&path, sample.to_file())?;
+	Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_front_matter() {
+		let sample = Sample::parse(2, "---\npart1: 11\npart2: 31\nsize: 7\n---\n3   4\n4   3\n").unwrap();
+		assert_eq!(sample.number, 2);
+		assert_eq!(sample.input, "3   4\n4   3");
+		assert_eq!(sample.expected(Part::One), Some("11"));
+		assert_eq!(sample.expected(Part::Two), Some("31"));
+		assert_eq!(sample.params, BTreeMap::from([(String::from("size"), String::from("7"))]));
+	}
+
+	#[test]
+	fn parses_without_front_matter() {
+		let sample = Sample::parse(1, "3   4\n4   3\n").unwrap();
+		assert_eq!(sample.input, "3   4\n4   3");
+		assert_eq!(sample.expected(Part::One), None);
+		assert!(sample.params.is_empty());
 	}
 
-	Ok(fs::read_to_string(path)?.trim().to_string())
+	#[test]
+	fn rejects_bad_front_matter() {
+		assert!(Sample::parse(1, "---\npart1: 11\n3   4\n").is_err());
+		assert!(Sample::parse(1, "---\npart1 11\n---\n3   4\n").is_err());
+	}
+
+	#[test]
+	fn writes_what_it_reads() {
+		let text = "---\npart1: 11\nsize: 7\n---\n3   4\n4   3\n";
+		assert_eq!(Sample::parse(1, text).unwrap().to_file(), text);
+		assert_eq!(Sample { input: String::from("1 2"), ..Default::default() }.to_file(), "1 2\n");
+	}
 }
@@ -0,0 +1,115 @@
+//! Every request to the site goes through here, so they are all throttled, and anything that can't
+//! change any more is only ever downloaded once
+
+use std::{fs, path::PathBuf, thread, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use reqwest::{ blocking::{ Client, RequestBuilder }, redirect::Policy };
+use serde::{ Deserialize, Serialize };
+
+use super::InputError;
+use crate::lib::config::{ config, profile, DEFAULT_BASE_URL };
+
+///The shortest time allowed between two requests, even across separate runs.
+const MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+///A cached response, stored in `http/<hash of url>.toml` in the profile's cache directory, since
+///inputs and puzzle pages differ between accounts.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+	url: String,
+	///When it was downloaded, in seconds since the Unix epoch.
+	fetched: u64,
+	body: String,
+}
+
+fn cache_dir() -> PathBuf {
+	config().profile_dir().join("http")
+}
+
+///FNV-1a, which unlike `DefaultHasher` is guaranteed to give the same file name on every build.
+fn cache_path(url: &str) -> PathBuf {
+	let hash = url.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+	cache_dir().join(format!("{hash:016x}.toml"))
+}
+
+fn cached(url: &str) -> Option<String> {
+	let entry: Entry = toml::from_str(&fs::read_to_string(cache_path(url)).ok()?).ok()?;
+	// Guards against hash collisions.
+	(entry.url == url).then_some(entry.body)
+}
+
+fn store(url: &str, body: &str) -> Result<(), InputError> {
+	let fetched = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+	let entry = Entry { url: url.to_string(), fetched, body: body.to_string() };
+	fs::create_dir_all(cache_dir())?;
+	fs::write(cache_path(url), toml::to_string(&entry).expect("entries are always valid TOML"))?;
+	Ok(())
+}
+
+///Sleeps until `MIN_INTERVAL` has passed since the last request, which is recorded in the cache
+///directory so separate runs in a loop are throttled too. It's shared by every profile. Anything
+///other than the real site, like a local server for testing, isn't throttled.
+fn throttle(base_url: &str) -> Result<(), InputError> {
+	if base_url != DEFAULT_BASE_URL {
+		return Ok(());
+	}
+	let dir = config().cache_dir.join("http");
+	let path = dir.join("last_request");
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+	if let Some(last) = fs::read_to_string(&path).ok().and_then(|text| text.trim().parse().ok()).map(Duration::from_millis) {
+		let wait = (last + MIN_INTERVAL).saturating_sub(now);
+		if !wait.is_zero() {
+			eprintln!("Waiting {wait:.1?} before the next request");
+			thread::sleep(wait);
+		}
+	}
+	fs::create_dir_all(dir)?;
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+	fs::write(path, now.as_millis().to_string())?;
+	Ok(())
+}
+
+///The session cookie from `.env`, which the site needs for anything that differs between users. The
+///default profile uses `token`, and every other one `token_<name>`.
+pub fn token() -> Result<String, InputError> {
+	let name = profile().map_or(String::from("token"), |profile| format!("token_{profile}"));
+	std::env::var(&name).ok().filter(|token| !token.trim().is_empty()).ok_or(InputError::MissingToken(name))
+}
+
+///Sends a request for `path` on `base_url` with the configured User-Agent and the session cookie.
+///Redirects aren't followed, since a bad session gets redirected to the login page rather than
+///getting an error.
+fn send(base_url: &str, token: &str, path: &str, build: impl FnOnce(&Client, String) -> RequestBuilder) -> Result<String, InputError> {
+	let client = Client::builder().user_agent(&config().user_agent).redirect(Policy::none()).build()?;
+	throttle(base_url)?;
+	let response = build(&client, format!("{base_url}{path}")).header("Cookie", format!("session={token}")).send()?;
+	match response.status() {
+		status if status.is_success() => Ok(response.text()?),
+		status if status.is_redirection() => Err(InputError::ExpiredSession),
+		status => Err(InputError::Status(status)),
+	}
+}
+
+///Gets a page from the site, e.g. `/2024/day/1/input`. Once `done` says a page can't change any more
+///it's kept and never requested again; until then it's downloaded every time.
+pub fn get(path: &str, done: impl Fn(&str) -> bool) -> Result<String, InputError> {
+	let url = format!("{}{path}", config().base_url);
+	if let Some(body) = cached(&url) {
+		return Ok(body);
+	}
+	let body = send(&config().base_url, &token()?, path, |client, url| client.get(url))?;
+	if done(&body) {
+		store(&url, &body)?;
+	}
+	Ok(body)
+}
+
+///Posts a form to the site. Replies are never cached.
+pub fn post(path: &str, form: &[(&str, &str)]) -> Result<String, InputError> {
+	post_to(&config().base_url, &token()?, path, form)
+}
+
+///Posts a form to `base_url` as whoever `token` belongs to, rather than to the configured site.
+pub fn post_to(base_url: &str, token: &str, path: &str, form: &[(&str, &str)]) -> Result<String, InputError> {
+	send(base_url, token, path, |client, url| client.post(url).form(form))
+}
@@ -0,0 +1,67 @@
+//! Debug output for solutions, shown or hidden depending on how they're being run
+//!
+//! Everything goes to stderr, so it never gets mixed up with answers. Use [`debug!`](crate::debug)
+//! for things worth seeing while working on a sample, and [`trace!`](crate::trace) for anything
+//! printed on every step.
+
+use std::sync::atomic::{ AtomicU8, Ordering };
+
+///How much gets printed, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+	///Only answers and errors.
+	Quiet,
+	///Timings and other notes from the runner.
+	Info,
+	Debug,
+	Trace,
+}
+
+impl Level {
+	const ALL: [Level; 4] = [Level::Quiet, Level::Info, Level::Debug, Level::Trace];
+
+	///Moves `steps` levels up (or down, if negative), stopping at either end.
+	pub fn adjusted(self, steps: isize) -> Level {
+		let index = (self as isize + steps).clamp(0, Level::ALL.len() as isize - 1);
+		Level::ALL[index as usize]
+	}
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+
+pub fn set_level(level: Level) {
+	LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+	Level::ALL[LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+///Whether output at `level` is shown. Check this before building anything expensive to print.
+pub fn enabled(level: Level) -> bool {
+	level <= self::level()
+}
+
+///Prints a line to stderr at [`Level::Info`].
+#[macro_export]
+macro_rules! info {
+	($($arg:tt)*) => {
+		if $crate::lib::log::enabled($crate::lib::log::Level::Info) { eprintln!($($arg)*) }
+	};
+}
+
+///Prints a line to stderr at [`Level::Debug`].
+#[macro_export]
+macro_rules! debug {
+	($($arg:tt)*) => {
+		if $crate::lib::log::enabled($crate::lib::log::Level::Debug) { eprintln!($($arg)*) }
+	};
+}
+
+///Prints a line to stderr at [`Level::Trace`].
+#[macro_export]
+macro_rules! trace {
+	($($arg:tt)*) => {
+		if $crate::lib::log::enabled($crate::lib::log::Level::Trace) { eprintln!($($arg)*) }
+	};
+}
@@ -0,0 +1,164 @@
+//! Counting how much heap is in use, so runs can report their peak and be stopped before they eat
+//! all of the machine's memory
+
+use std::{alloc::{GlobalAlloc, Layout, System}, io::Write, sync::atomic::{AtomicUsize, Ordering}};
+
+use super::puzzle::Part;
+
+///Wraps the system allocator, keeping track of how many bytes are allocated.
+struct Counting;
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+///The most the heap may hold before the program is aborted. Zero means no limit.
+static LIMIT: AtomicUsize = AtomicUsize::new(0);
+///Which part is running, packed as `year * 1000 + day * 10 + part` so the allocator can name it
+///without allocating. Zero when no part is.
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+///How many parts that timed out are still running. They keep allocating, so while there are any,
+///nothing measured here can be blamed on one part.
+static ABANDONED: AtomicUsize = AtomicUsize::new(0);
+
+impl Counting {
+	///Counts `size` more bytes, aborting if that goes over the limit.
+	fn grow(&self, size: usize) {
+		let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+		PEAK.fetch_max(current, Ordering::Relaxed);
+		let limit = LIMIT.load(Ordering::Relaxed);
+		if limit != 0 && current > limit {
+			over_limit(limit);
+		}
+	}
+}
+
+///Reports which part went over the limit and aborts. Nothing here can allocate, since it runs
+///inside the allocator, so the message is written into a buffer on the stack.
+#[cold]
+fn over_limit(limit: usize) -> ! {
+	let mut buffer = [0_u8; 512];
+	let mut message = std::io::Cursor::new(&mut buffer[..]);
+	let running = RUNNING.load(Ordering::Relaxed);
+	let _ = match running {
+		0 => write!(message, "error: went over the memory limit of "),
+		_ => write!(message, "error: {} day {} part {} went over the memory limit of ", running / 1000, running / 10 % 100, running % 10),
+	};
+	let _ = write_size(&mut message, limit);
+	let _ = match ABANDONED.load(Ordering::Relaxed) {
+		0 => writeln!(message, ", aborting"),
+		n => writeln!(message, ", aborting (though {n} part(s) that timed out are still running, so it may not be this one's fault)"),
+	};
+	let written = message.position() as usize;
+	let _ = std::io::stderr().write_all(&buffer[..written]);
+	std::process::abort();
+}
+
+unsafe impl GlobalAlloc for Counting {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		self.grow(layout.size());
+		// SAFETY: the caller upholds `alloc`'s contract, which is passed on unchanged.
+		unsafe { System.alloc(layout) }
+	}
+
+	unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+		self.grow(layout.size());
+		// SAFETY: as for `alloc`.
+		unsafe { System.alloc_zeroed(layout) }
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+		// SAFETY: as for `alloc`.
+		unsafe { System.dealloc(ptr, layout) }
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		if new_size > layout.size() {
+			self.grow(new_size - layout.size());
+		} else {
+			CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+		}
+		// SAFETY: as for `alloc`.
+		unsafe { System.realloc(ptr, layout, new_size) }
+	}
+}
+
+///Sets how big the heap may get before the program is aborted, or removes the limit.
+pub fn set_limit(limit: Option<usize>) {
+	LIMIT.store(limit.unwrap_or(0), Ordering::Relaxed);
+}
+
+pub fn limit() -> Option<usize> {
+	Some(LIMIT.load(Ordering::Relaxed)).filter(|&limit| limit > 0)
+}
+
+///Bytes allocated right now.
+pub fn current() -> usize {
+	CURRENT.load(Ordering::Relaxed)
+}
+
+///Starts measuring the peak for a part, returning what's already allocated to measure it from.
+pub fn start(year: usize, day: usize, part: Part) -> usize {
+	RUNNING.store(year * 1000 + day * 10 + if part.is_one() { 1 } else { 2 }, Ordering::Relaxed);
+	let current = current();
+	PEAK.store(current, Ordering::Relaxed);
+	current
+}
+
+///Stops measuring, returning how far the heap grew past `baseline` since [`start`]. Returns nothing
+///if a part that timed out is still running, since its allocations would be counted too.
+pub fn finish(baseline: usize) -> Option<usize> {
+	RUNNING.store(0, Ordering::Relaxed);
+	let peak = PEAK.load(Ordering::Relaxed).saturating_sub(baseline);
+	(ABANDONED.load(Ordering::Relaxed) == 0).then_some(peak)
+}
+
+///Notes that a part was given up on while still running.
+pub fn abandoned() {
+	ABANDONED.fetch_add(1, Ordering::Relaxed);
+}
+
+///Notes that a part that was given up on has finally finished.
+pub fn abandoned_finished() {
+	ABANDONED.fetch_sub(1, Ordering::Relaxed);
+}
+
+fn write_size(out: &mut impl Write, bytes: usize) -> std::io::Result<()> {
+	const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+	let mut size = bytes as f64;
+	let mut unit = 0;
+	while size >= 1024.0 && unit < UNITS.len() - 1 {
+		size /= 1024.0;
+		unit += 1;
+	}
+	match unit {
+		0 => write!(out, "{bytes} B"),
+		_ => write!(out, "{size:.2} {}", UNITS[unit]),
+	}
+}
+
+///Formats a number of bytes like `1.50 MiB`.
+pub fn format_size(bytes: usize) -> String {
+	let mut out = vec![];
+	write_size(&mut out, bytes).expect("writing to a Vec can't fail");
+	String::from_utf8(out).expect("sizes are ASCII")
+}
+
+///Reads a size like `512M`, `4G` or `1.5GiB`, in bytes unless it has a unit. Units are powers of 1024.
+pub fn parse_size(text: &str) -> Result<usize, String> {
+	let text = text.trim();
+	let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+	let (number, unit) = text.split_at(split);
+	let number: f64 = number.parse().map_err(|_| format!("expected a size like 512M or 4G, got {text:?}"))?;
+	let scale: u64 = match unit.trim().to_ascii_uppercase().trim_end_matches("IB").trim_end_matches('B') {
+		"" => 1,
+		"K" => 1 << 10,
+		"M" => 1 << 20,
+		"G" => 1 << 30,
+		"T" => 1 << 40,
+		_ => return Err(format!("unknown unit {unit:?}, expected K, M, G or T")),
+	};
+	Ok((number * scale as f64) as usize)
+}
@@ -1,13 +1,25 @@
+pub mod calendar;
+pub mod config;
 pub mod input;
+pub mod html;
 pub mod grid;
 pub mod pathfind;
 pub mod direction;
+pub mod puzzle;
+pub mod timing;
+pub mod log;
+pub mod memory;
+pub mod submit;
 
 pub mod prelude {
 	#![allow(unused_imports)]
 	pub use super::grid::*;
 	pub use super::direction::*;
 	pub use super::pathfind::*;
+	pub use super::puzzle::*;
+	pub use super::timing::parsing;
+	pub use super::log::{ self, Level };
+	pub use crate::{ info, debug, trace };
 
 	pub use std::fmt::{ Display, Debug };
 	pub use std::collections::{ HashMap, HashSet };
@@ -0,0 +1,38 @@
+//! Types passed from the runner into each day's solution
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use anyhow::{ Result, anyhow };
+use strum::{ Display, EnumIs, EnumString };
+
+///Which half of a day's puzzle is being solved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display, EnumIs, EnumString)]
+pub enum Part {
+	#[default]
+	#[strum(serialize = "1")]
+	One,
+	#[strum(serialize = "2")]
+	Two,
+}
+
+///Everything a solution may need to know about the current run besides its input.
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+	///True when running against a pasted sample instead of the real input.
+	pub sample: bool,
+	///Values of the day's [`Param`]s for this run.
+	pub params: BTreeMap<String, String>,
+}
+
+impl Context {
+	///Reads one of the parameters the day declared in its `PARAMS`.
+	pub fn param<T: FromStr>(&self, name: &str) -> Result<T> where T::Err: std::fmt::Display {
+		let value = self.params.get(name).ok_or_else(|| anyhow!("no parameter named {name}; add it to PARAMS"))?;
+		value.parse().map_err(|e| anyhow!("parameter {name}={value}: {e}"))
+	}
+}
+
+///A value that differs between the real input and samples, such as the size of a grid, declared by a
+///day as `pub const PARAMS: &[Param] = &[("name", "default")];`. The default is for the real input;
+///samples override it in their front matter, and runs can override it with `--param name=value`.
+pub type Param = (&'static str, &'static str);
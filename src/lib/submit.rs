@@ -0,0 +1,168 @@
+//! Submitting answers and making sense of the reply
+
+use super::{ config::config, html, input::http, prelude::* };
+
+///What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, EnumIs)]
+pub enum Response {
+	Correct,
+	Wrong,
+	TooHigh,
+	TooLow,
+	///Answered too recently. Holds the site's "You have 1m 2s left to wait" message if it gave one.
+	RateLimited(Option<String>),
+	///The part has already been solved, or part 1 has not been solved yet.
+	WrongLevel,
+	///Anything else, holding the text of the reply's `<article>`.
+	Unrecognized(String),
+}
+
+impl Display for Response {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Response::Correct => write!(f, "That's the right answer!"),
+			Response::Wrong => write!(f, "That's not the right answer."),
+			Response::TooHigh => write!(f, "That's not the right answer; it's too high."),
+			Response::TooLow => write!(f, "That's not the right answer; it's too low."),
+			Response::RateLimited(Some(wait)) => write!(f, "Answered too recently. {wait}"),
+			Response::RateLimited(None) => write!(f, "Answered too recently."),
+			Response::WrongLevel => write!(f, "That part is not open, or has already been solved."),
+			Response::Unrecognized(text) => write!(f, "Unrecognized response: {text}"),
+		}
+	}
+}
+
+impl Response {
+	///Reads the verdict out of the page the site replies with.
+	pub fn parse(page: &str) -> Response {
+		let article = page.split_once("<article>").and_then(|(_, rest)| rest.split_once("</article>")).map_or(page, |(article, _)| article);
+		if article.contains("That's the right answer") {
+			Response::Correct
+		} else if article.contains("answer too recently") {
+			let wait = article.split_once("You have ").and_then(|(_, rest)| rest.split_once(" left to wait")).map(|(time, _)| format!("You have {time} left to wait."));
+			Response::RateLimited(wait)
+		} else if article.contains("not the right answer") {
+			if article.contains("too high") {
+				Response::TooHigh
+			} else if article.contains("too low") {
+				Response::TooLow
+			} else {
+				Response::Wrong
+			}
+		} else if article.contains("solving the right level") {
+			Response::WrongLevel
+		} else {
+			Response::Unrecognized(html::text(article).trim().to_string())
+		}
+	}
+}
+
+///Posts an answer for one part of a day.
+pub fn submit(year: usize, day: usize, part: Part, answer: &str) -> Result<Response> {
+	submit_to(&config().base_url, &http::token()?, year, day, part, answer)
+}
+
+///Posts an answer to `base_url` as whoever `token` belongs to, rather than to the configured site.
+pub fn submit_to(base_url: &str, token: &str, year: usize, day: usize, part: Part, answer: &str) -> Result<Response> {
+	let level = part.to_string();
+	let page = http::post_to(base_url, token, &format!("/{year}/day/{day}/answer"), &[("level", level.as_str()), ("answer", answer)])?;
+	Ok(Response::parse(&page))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	///Wraps an `<article>` in enough of a page to look like the site's reply.
+	fn page(article: &str) -> String {
+		format!("<!DOCTYPE html>\n<html><head><title>Day 1 - Advent of Code 2024</title></head><body><main>\n<article>{article}</article>\n</main></body></html>")
+	}
+
+	#[test]
+	fn correct() {
+		let reply = page(r#"<p>That's the right answer!  You are <span class="day-success">one gold star</span> closer to finding the Chief Historian. <a href="/2024/day/1#part2">[Continue to Part Two]</a></p>"#);
+		assert_eq!(Response::parse(&reply), Response::Correct);
+	}
+
+	#[test]
+	fn wrong() {
+		let reply = page(r#"<p>That's not the right answer.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href="/2024/about">about page</a>, or you can ask for hints on the <a href="https://www.reddit.com/r/adventofcode/" target="_blank">subreddit</a>.  Please wait one minute before trying again. <a href="/2024/day/1">[Return to Day 1]</a></p>"#);
+		assert_eq!(Response::parse(&reply), Response::Wrong);
+	}
+
+	#[test]
+	fn too_high() {
+		let reply = page(r#"<p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href="/2024/day/1">[Return to Day 1]</a></p>"#);
+		assert_eq!(Response::parse(&reply), Response::TooHigh);
+	}
+
+	#[test]
+	fn too_low() {
+		let reply = page(r#"<p>That's not the right answer; your answer is too low.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href="/2024/day/1">[Return to Day 1]</a></p>"#);
+		assert_eq!(Response::parse(&reply), Response::TooLow);
+	}
+
+	#[test]
+	fn rate_limited() {
+		let reply = page(r#"<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait. <a href="/2024/day/1">[Return to Day 1]</a></p>"#);
+		assert_eq!(Response::parse(&reply), Response::RateLimited(Some(String::from("You have 34s left to wait."))));
+
+		let reply = page(r#"<p>You gave an answer too recently; you have to wait after submitting an answer before trying again. <a href="/2024/day/1">[Return to Day 1]</a></p>"#);
+		assert_eq!(Response::parse(&reply), Response::RateLimited(None));
+	}
+
+	#[test]
+	fn wrong_level() {
+		let reply = page(r#"<p>You don't seem to be solving the right level.  Did you already complete it? <a href="/2024/day/1">[Return to Day 1]</a></p>"#);
+		assert_eq!(Response::parse(&reply), Response::WrongLevel);
+	}
+
+	///Serves `reply` to one request on a local port, returning the port and what was sent to it.
+	fn serve_once(reply: String) -> (u16, std::thread::JoinHandle<String>) {
+		use std::io::{ BufRead, BufReader, Read, Write };
+
+		let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+		let port = listener.local_addr().unwrap().port();
+		let server = std::thread::spawn(move || {
+			let (mut stream, _) = listener.accept().unwrap();
+			let mut reader = BufReader::new(stream.try_clone().unwrap());
+			let mut request = String::new();
+			let mut length = 0;
+			loop {
+				let mut line = String::new();
+				reader.read_line(&mut line).unwrap();
+				if let Some((name, value)) = line.split_once(':') && name.eq_ignore_ascii_case("content-length") {
+					length = value.trim().parse().unwrap();
+				}
+				request.push_str(&line);
+				if line == "\r\n" {
+					break;
+				}
+			}
+			let mut body = vec![0; length];
+			reader.read_exact(&mut body).unwrap();
+			request.push_str(&String::from_utf8(body).unwrap());
+			write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{reply}", reply.len()).unwrap();
+			request
+		});
+		(port, server)
+	}
+
+	#[test]
+	fn submits_to_a_server() {
+		let (port, server) = serve_once(page("<p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p>"));
+		let response = submit_to(&format!("http://127.0.0.1:{port}"), "abc123", 2024, 1, Part::Two, "42").unwrap();
+		assert_eq!(response, Response::Correct);
+
+		let request = server.join().unwrap();
+		assert!(request.starts_with("POST /2024/day/1/answer HTTP/1.1\r\n"), "{request}");
+		assert!(request.contains("session=abc123"), "{request}");
+		assert!(request.ends_with("level=2&answer=42"), "{request}");
+	}
+
+	#[test]
+	fn unrecognized() {
+		let reply = page("<p>Something <em>odd</em> happened.</p>");
+		assert_eq!(Response::parse(&reply), Response::Unrecognized(String::from("Something odd happened.")));
+	}
+}
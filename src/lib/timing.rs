@@ -0,0 +1,20 @@
+//! Lets solutions report how long they spend parsing, separately from solving
+
+use std::{cell::Cell, time::{Duration, Instant}};
+
+thread_local! {
+	static PARSE_TIME: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+///Runs `parse`, counting the time it takes as parsing instead of solving.
+pub fn parsing<T>(parse: impl FnOnce() -> T) -> T {
+	let start = Instant::now();
+	let output = parse();
+	PARSE_TIME.with(|time| time.set(time.get() + start.elapsed()));
+	output
+}
+
+///Returns the parse time recorded on this thread since the last call, and resets it.
+pub fn take_parse_time() -> Duration {
+	PARSE_TIME.with(|time| time.replace(Duration::ZERO))
+}
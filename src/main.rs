@@ -1,100 +1,430 @@
 #![allow(special_module_name)]
 
+use anyhow::bail;
+use clap::{ Args, Parser, Subcommand };
+
+use lib::config::{ self, config };
+use lib::log::{ self, Level };
+use lib::prelude::Part;
+use output::{ Format, StdoutToStderr };
+use runner::Job;
+
 //Making this pub silences dead_code warnings on pub members.
 pub mod lib;
+mod answers;
+mod bench;
+mod extract;
+mod history;
+mod output;
+mod runner;
+mod scaffold;
+mod status;
+mod store;
+mod watch;
 mod years;
 
-#[cfg(feature = "default")]
-const YEAR: usize = usize::max_value();
-#[cfg(all(feature = "year2015", not(feature = "default")))]
-const YEAR: usize = 2015;
-#[cfg(all(feature = "year2016", not(feature = "default")))]
-const YEAR: usize = 2016;
-#[cfg(all(feature = "year2017", not(feature = "default")))]
-const YEAR: usize = 2017;
-#[cfg(all(feature = "year2018", not(feature = "default")))]
-const YEAR: usize = 2018;
-#[cfg(all(feature = "year2019", not(feature = "default")))]
-const YEAR: usize = 2019;
-#[cfg(all(feature = "year2020", not(feature = "default")))]
-const YEAR: usize = 2020;
-#[cfg(all(feature = "year2021", not(feature = "default")))]
-const YEAR: usize = 2021;
-#[cfg(all(feature = "year2022", not(feature = "default")))]
-const YEAR: usize = 2022;
-#[cfg(all(feature = "year2023", not(feature = "default")))]
-const YEAR: usize = 2023;
-#[cfg(all(feature = "year2024", not(feature = "default")))]
-const YEAR: usize = 2024;
-
-#[cfg(feature = "default")]
-const DAY: usize = usize::max_value();
-#[cfg(all(feature = "day1", not(feature = "default")))]
-const DAY: usize = 1;
-#[cfg(all(feature = "day2", not(feature = "default")))]
-const DAY: usize = 2;
-#[cfg(all(feature = "day3", not(feature = "default")))]
-const DAY: usize = 3;
-#[cfg(all(feature = "day4", not(feature = "default")))]
-const DAY: usize = 4;
-#[cfg(all(feature = "day5", not(feature = "default")))]
-const DAY: usize = 5;
-#[cfg(all(feature = "day6", not(feature = "default")))]
-const DAY: usize = 6;
-#[cfg(all(feature = "day7", not(feature = "default")))]
-const DAY: usize = 7;
-#[cfg(all(feature = "day8", not(feature = "default")))]
-const DAY: usize = 8;
-#[cfg(all(feature = "day9", not(feature = "default")))]
-const DAY: usize = 9;
-#[cfg(all(feature = "day10", not(feature = "default")))]
-const DAY: usize = 10;
-#[cfg(all(feature = "day11", not(feature = "default")))]
-const DAY: usize = 11;
-#[cfg(all(feature = "day12", not(feature = "default")))]
-const DAY: usize = 12;
-#[cfg(all(feature = "day13", not(feature = "default")))]
-const DAY: usize = 13;
-#[cfg(all(feature = "day14", not(feature = "default")))]
-const DAY: usize = 14;
-#[cfg(all(feature = "day15", not(feature = "default")))]
-const DAY: usize = 15;
-#[cfg(all(feature = "day16", not(feature = "default")))]
-const DAY: usize = 16;
-#[cfg(all(feature = "day17", not(feature = "default")))]
-const DAY: usize = 17;
-#[cfg(all(feature = "day18", not(feature = "default")))]
-const DAY: usize = 18;
-#[cfg(all(feature = "day19", not(feature = "default")))]
-const DAY: usize = 19;
-#[cfg(all(feature = "day20", not(feature = "default")))]
-const DAY: usize = 20;
-#[cfg(all(feature = "day20", not(feature = "default")))]
-const DAY: usize = 20;
-#[cfg(all(feature = "day21", not(feature = "default")))]
-const DAY: usize = 21;
-#[cfg(all(feature = "day22", not(feature = "default")))]
-const DAY: usize = 22;
-#[cfg(all(feature = "day23", not(feature = "default")))]
-const DAY: usize = 23;
-#[cfg(all(feature = "day24", not(feature = "default")))]
-const DAY: usize = 24;
-#[cfg(all(feature = "day25", not(feature = "default")))]
-const DAY: usize = 25;
+///Runs Advent of Code solutions against their inputs.
+#[derive(Debug, Parser)]
+#[command(version, args_conflicts_with_subcommands = true)]
+struct Cli {
+	#[command(subcommand)]
+	command: Option<Command>,
+	#[command(flatten)]
+	run: Option<RunArgs>,
+	#[command(flatten)]
+	bench: BenchArgs,
+	#[command(flatten)]
+	output: OutputArgs,
+	///Print more of what solutions log. Repeat for even more.
+	#[arg(short, long, action = clap::ArgAction::Count, global = true)]
+	verbose: u8,
+	///Print less of what solutions log. Repeat for even less.
+	#[arg(short, long, action = clap::ArgAction::Count, global = true)]
+	quiet: u8,
+	///Use the inputs and answers of the account whose session cookie is `token_<NAME>` in .env.
+	#[arg(long, value_name = "NAME", global = true)]
+	profile: Option<String>,
+	///Abort the whole run, not just the part, once the program's heap grows past this size, e.g. `4G`
+	///or `512M`. Rows already printed are kept.
+	#[arg(long, value_name = "SIZE", value_parser = lib::memory::parse_size, global = true)]
+	max_memory: Option<usize>,
+	///Give up on a part once it has run for this many seconds. 0 lets it run forever.
+	#[arg(long, value_name = "SECONDS", default_value_t = 60, global = true)]
+	timeout: u64,
+}
 
-fn main() -> anyhow::Result<()> {
-	dotenv::dotenv().unwrap();
+impl Cli {
+	///How much gets logged before `-v` and `-q` are applied: solutions are chatty while working on a
+	///sample, and silent when many of them run or when the output is meant for another program.
+	fn default_level(&self) -> Level {
+		match (&self.command, &self.run) {
+			(Some(Command::RunAll { .. } | Command::Verify { .. }), _) => Level::Quiet,
+			(None, Some(_)) if self.bench.bench.is_some() || self.output.format == Format::Json => Level::Quiet,
+			(None, Some(RunArgs { sample: Some(_), .. })) | (Some(Command::Watch { sample: Some(_), .. }), _) => Level::Debug,
+			_ => Level::Info,
+		}
+	}
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+	///Runs every written part of every day with a downloaded input and prints a table of the results.
+	RunAll {
+		///Only run days from this year.
+		year: Option<usize>,
+		#[command(flatten)]
+		bench: BenchArgs,
+		#[command(flatten)]
+		output: OutputArgs,
+	},
+	///Runs every written part with a downloaded input and checks it against the recorded answers.toml.
+	Verify {
+		///Only check days from this year.
+		year: Option<usize>,
+		///Only check this day.
+		day: Option<usize>,
+		///Save the answers of parts that have none recorded yet. Only use this once they have been accepted.
+		#[arg(long)]
+		record: bool,
+		///Check the inputs of every profile in turn, starting with the default one.
+		#[arg(long, conflicts_with = "profile")]
+		all_profiles: bool,
+	},
+	///Solves one part against the real input and submits the answer, recording it if it is right.
+	Submit {
+		year: usize,
+		day: usize,
+		///Which part to submit (1 or 2).
+		#[arg(long)]
+		part: Part,
+	},
+	///Lists which days have a solution in src/years.
+	List {
+		///Only list this year.
+		year: Option<usize>,
+	},
+	///Shows a grid of which days have code, stars, samples and inputs.
+	Status {
+		///Only show this year.
+		year: Option<usize>,
+	},
+	///Creates a new day from sample_day.rs, with an empty sample, and downloads its input.
+	New {
+		year: usize,
+		day: usize,
+	},
+	///Rebuilds and reruns a day every time its source, sample or input is saved.
+	Watch {
+		year: usize,
+		day: usize,
+		///Which part to solve (1 or 2). Solves every written part if omitted.
+		#[arg(long)]
+		part: Option<Part>,
+		///Use a pasted sample instead of the real input. Optionally takes which sample to use.
+		#[arg(long, num_args = 0..=1, default_missing_value = "1", value_name = "N")]
+		sample: Option<usize>,
+	},
+	///Shows the puzzle's description, including part 2 once it has unlocked.
+	Read {
+		year: usize,
+		day: usize,
+	},
+	///Lists the code blocks on a puzzle's page, or saves the chosen ones as samples.
+	Extract {
+		year: usize,
+		day: usize,
+		///Read a saved copy of the page instead of downloading it.
+		#[arg(long, value_name = "PAGE.html")]
+		file: Option<std::path::PathBuf>,
+		///Save block N as the next free sample. Can be repeated.
+		#[arg(long = "block", value_name = "N")]
+		blocks: Vec<usize>,
+		///Put the page's highlighted answers in the saved samples' front matter.
+		#[arg(long, requires = "blocks")]
+		answers: bool,
+	},
+}
+
+#[derive(Debug, Args)]
+struct BenchArgs {
+	///Run each part N times and report min/median/mean times against the saved baseline.
+	#[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+	bench: Option<u64>,
+	///Replace saved baselines with this benchmark's medians.
+	#[arg(long, requires = "bench")]
+	save_baseline: bool,
+}
+
+#[derive(Debug, Args)]
+struct OutputArgs {
+	///How to print results.
+	#[arg(long, value_enum, default_value_t, conflicts_with = "bench")]
+	format: Format,
+	///Also show how much heap each part used at its peak.
+	#[arg(long)]
+	memory: bool,
+}
+
+///Runs a single day.
+#[derive(Debug, Args)]
+struct RunArgs {
+	///Year of the event, e.g. 2024.
+	year: usize,
+	///Day of the puzzle, from 1 to 25.
+	day: usize,
+	///Which part of the puzzle to solve (1 or 2). Solves every written part if omitted.
+	#[arg(long)]
+	part: Option<Part>,
+	///Use a pasted sample instead of the real input. Optionally takes which sample to use.
+	#[arg(long, num_args = 0..=1, default_missing_value = "1", value_name = "N")]
+	sample: Option<usize>,
+	///Read the input from this file instead, or from stdin if it is `-`.
+	#[arg(long, value_name = "PATH", conflicts_with = "sample")]
+	input: Option<String>,
+	///Overrides one of the day's parameters, e.g. `--param width=11`. Can be repeated.
+	#[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+	params: Vec<(String, String)>,
+}
+
+fn parse_param(arg: &str) -> Result<(String, String), String> {
+	let (name, value) = arg.split_once('=').ok_or_else(|| format!("expected NAME=VALUE, got {arg:?}"))?;
+	Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+fn run(args: RunArgs, bench: BenchArgs, output: OutputArgs) -> anyhow::Result<()> {
+	lib::input::check_day(args.year, args.day)?;
+	let Some(day) = years::find(args.year, args.day) else {
+		bail!("{} day {} has no solution in src/years", args.year, args.day);
+	};
+	let parts = match args.part {
+		Some(part) if day.solver(part).is_none() => bail!("{} day {} has no part {part}", args.year, args.day),
+		Some(part) => vec![part],
+		None => day.parts(),
+	};
+
+	let (input, sample) = match (args.sample, &args.input) {
+		(Some(number), _) => {
+			let sample = lib::input::sample(args.year, args.day, number)?;
+			(sample.input.clone(), Some(sample))
+		},
+		(None, Some(path)) => (lib::input::read_input(path)?, None),
+		(None, None) => (lib::input::input(args.year, args.day)?, None),
+	};
+
+	let ctx = day.context(sample.as_ref(), &args.params)?;
+	if let Some(iterations) = bench.bench {
+		let baseline = sample.is_none() && args.input.is_none();
+		return bench::bench(&[Job { day, parts, input, ctx, baseline }], iterations as usize, bench.save_baseline);
+	}
+
+	if output.format == Format::Json {
+		let mut failed = false;
+		let stdout = StdoutToStderr::new()?;
+		for part in parts {
+			let outcome = runner::run(day, part, input.clone(), &ctx);
+			stdout.println(&output::json(&outcome))?;
+			failed |= !outcome.status.is_solved();
+		}
+		if failed {
+			bail!("Some parts did not produce an answer");
+		}
+		return Ok(());
+	}
+
+	for part in parts {
+		let outcome = runner::run(day, part, input.clone(), &ctx);
+		match &outcome.status {
+			runner::Status::Solved(answer) if args.part.is_some() => println!("{answer}"),
+			runner::Status::Solved(answer) => println!("Part {part}: {answer}"),
+			status => bail!("Part {part}: {status}"),
+		}
+		if let (runner::Status::Solved(answer), Some(expected)) = (&outcome.status, sample.as_ref().and_then(|s| s.expected(part)))
+			&& answer != expected {
+			eprintln!("Part {part} does not match the sample's answer of {expected}");
+		}
+		match output.memory {
+			true => info!("Parsed in {:.2?}, solved in {:.2?}, peak memory {}", outcome.parse, outcome.solve, runner::format_memory(outcome.memory)),
+			false => info!("Parsed in {:.2?}, solved in {:.2?}", outcome.parse, outcome.solve),
+		}
+	}
+	Ok(())
+}
+
+fn submit(year: usize, day: usize, part: Part) -> anyhow::Result<()> {
+	lib::input::check_day(year, day)?;
+	let Some(solution) = years::find(year, day) else {
+		bail!("{year} day {day} has no solution in src/years");
+	};
+	if solution.solver(part).is_none() {
+		bail!("{year} day {day} has no part {part}");
+	}
+
+	let input = lib::input::input(year, day)?;
+	let outcome = runner::run(solution, part, input, &solution.context(None, &[])?);
+	let runner::Status::Solved(answer) = outcome.status else {
+		bail!("Part {part}: {}", outcome.status);
+	};
 
-	if cfg!(feature = "default") {
-		unimplemented!("Use --no-default-features and --feature 'yearYYYY dayDD [sample] [part2]'")
+	let mut history = history::load_history(year)?;
+	if let Err(reason) = history::check(history.get(day, part).map_or(&[], Vec::as_slice), &answer) {
+		bail!("Not submitting {answer}. {reason}");
 	}
 
-	let input = if cfg!(any(feature = "sample", feature = "sample2")) {
-		lib::input::sample(YEAR, DAY)?
-	} else {
-		lib::input::input(YEAR, DAY)?
+	println!("Submitting {answer} for {year} day {day} part {part}");
+	let response = lib::submit::submit(year, day, part, &answer)?;
+	println!("{response}");
+	if let Some(feedback) = history::Feedback::from_response(&response) {
+		let mut guesses = history.get(day, part).cloned().unwrap_or_default();
+		guesses.push(history::Guess { answer: answer.clone(), feedback });
+		history.set(day, part, guesses);
+		history.save()?;
+	}
+	if response.is_correct() {
+		answers::record(year, day, part, answer)?;
+	}
+	Ok(())
+}
+
+///Prints which days have a solution, for every event or just one year.
+fn list(year: Option<usize>) -> anyhow::Result<()> {
+	if let Some(year) = year {
+		lib::input::check_year(year)?;
+	}
+	let mut part1_only = false;
+	for y in year.map_or(lib::calendar::FIRST_YEAR..=lib::calendar::latest_year(), |y| y..=y) {
+		let days: Vec<String> = years::DAYS.iter().filter(|d| d.year == y).map(|d| {
+			let only_one = d.part2.is_none();
+			part1_only |= only_one;
+			format!("{}{}", d.day, if only_one { "*" } else { "" })
+		}).collect();
+		match days.len() {
+			0 => println!("{y}  none"),
+			n => println!("{y}  {n:>2}/{}: {}", lib::calendar::days_in(y), days.join(" ")),
+		}
+	}
+	if part1_only {
+		println!("* only part 1 has been written");
+	}
+	Ok(())
+}
+
+///Collects the days with a downloaded input, optionally only from one year or only one day.
+fn cached_days(year: Option<usize>, day: Option<usize>) -> anyhow::Result<Vec<Job>> {
+	match (year, day) {
+		(Some(year), Some(day)) => lib::input::check_day(year, day)?,
+		(Some(year), None) => lib::input::check_year(year)?,
+		_ => {},
+	}
+	let days: Vec<_> = years::DAYS.iter().filter(|d| year.is_none_or(|y| d.year == y) && day.is_none_or(|n| d.day == n)).collect();
+	if days.is_empty() {
+		bail!("No solutions found in src/years{}", year.map_or(String::new(), |y| format!(" for {y}")));
+	}
+
+	let mut jobs = vec![];
+	for d in days {
+		let Some(input) = lib::input::cached_input(d.year, d.day) else {
+			eprintln!("Skipping {} day {}: input has not been downloaded", d.year, d.day);
+			continue;
+		};
+		jobs.push(Job { day: d, parts: d.parts(), input, ctx: d.context(None, &[])?, baseline: true });
+	}
+	Ok(jobs)
+}
+
+///Checks the answers of the current profile, or of every profile one after the other.
+fn verify(year: Option<usize>, day: Option<usize>, record: bool, all_profiles: bool) -> anyhow::Result<()> {
+	let profiles = match all_profiles {
+		true => std::iter::once(None).chain(config().profiles.iter().cloned().map(Some)).collect(),
+		false => vec![config::profile()],
 	};
+	let mut passed = true;
+	// A profile with nothing to check would otherwise pass without checking anything.
+	let mut empty = vec![];
+	for (i, profile) in profiles.iter().enumerate() {
+		let name = profile.as_deref().unwrap_or("default");
+		if all_profiles {
+			println!("{}Profile {name}", if i == 0 { "" } else { "\n" });
+		}
+		config::set_profile(profile.as_deref());
+		let jobs = cached_days(year, day)?;
+		if jobs.is_empty() {
+			println!("No inputs have been downloaded");
+			empty.push(name);
+			continue;
+		}
+		passed &= answers::verify(&jobs, record)?;
+	}
+	if !empty.is_empty() {
+		bail!("Nothing was checked for {}", empty.iter().map(|name| format!("profile {name}")).collect::<Vec<_>>().join(", "));
+	}
+	if !passed {
+		bail!("Some answers did not match");
+	}
+	Ok(())
+}
 
-	println!("{}", years::puzzle(input)?);
+fn run_all(year: Option<usize>, bench: BenchArgs, output: OutputArgs) -> anyhow::Result<()> {
+	let jobs = cached_days(year, None)?;
+	if let Some(iterations) = bench.bench {
+		return bench::bench(&jobs, iterations as usize, bench.save_baseline);
+	}
+
+	let json = match output.format {
+		Format::Json => Some(StdoutToStderr::new()?),
+		Format::Text => {
+			runner::print_header(output.memory);
+			None
+		},
+	};
+	for Job { day, parts, input, ctx, .. } in jobs {
+		for part in parts {
+			let outcome = runner::run(day, part, input.clone(), &ctx);
+			match &json {
+				Some(stdout) => stdout.println(&output::json(&outcome))?,
+				None => runner::print_row(&outcome, output.memory),
+			}
+		}
+	}
+	Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+	// .env is only needed for the token, so running without one is fine until something asks for it.
+	if let Err(e) = dotenv::dotenv() && !e.not_found() {
+		eprintln!("Could not load .env: {e}");
+	}
+	let cli = Cli::parse();
+	log::set_level(cli.default_level().adjusted(cli.verbose as isize - cli.quiet as isize));
+	lib::memory::set_limit(cli.max_memory);
+	runner::set_timeout(Some(std::time::Duration::from_secs(cli.timeout)).filter(|limit| !limit.is_zero()));
+	if let Some(name) = &cli.profile {
+		if !config().profiles.contains(name) {
+			bail!("There is no profile called {name}. Add `token_{name}=` followed by its session cookie to .env.");
+		}
+		config::set_profile(Some(name));
+	}
+
+	match (cli.command, cli.run) {
+		(Some(Command::RunAll { year, bench, output }), _) => run_all(year, bench, output)?,
+		(Some(Command::Verify { year, day, record, all_profiles }), _) => verify(year, day, record, all_profiles)?,
+		(Some(Command::Submit { year, day, part }), _) => submit(year, day, part)?,
+		(Some(Command::List { year }), _) => list(year)?,
+		(Some(Command::Status { year }), _) => status::status(year)?,
+		(Some(Command::New { year, day }), _) => scaffold::new_day(year, day)?,
+		(Some(Command::Watch { year, day, part, sample }), _) => watch::watch(year, day, part, sample)?,
+		(Some(Command::Read { year, day }), _) => {
+			let color = std::io::IsTerminal::is_terminal(&std::io::stdout());
+			print!("{}", lib::html::to_ansi(&lib::input::description(year, day)?, 100, color));
+		},
+		(Some(Command::Extract { year, day, file, blocks, answers }), _) => {
+			let page = match file {
+				Some(path) => std::fs::read_to_string(path)?,
+				None => lib::input::puzzle_page(year, day)?,
+			};
+			extract::extract(year, day, &page, &blocks, answers)?;
+		},
+		(None, Some(args)) => run(args, cli.bench, cli.output)?,
+		(None, None) => bail!("Expected a year and day, or a subcommand. See --help."),
+	}
 	Ok(())
 }
@@ -0,0 +1,92 @@
+//! Machine-readable results, kept apart from whatever solutions print
+
+use std::io::Write;
+
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::runner::{ Outcome, Status };
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+	///Answers and tables for reading.
+	#[default]
+	Text,
+	///One JSON object per line for each part, with anything the solutions print sent to stderr.
+	Json,
+}
+
+///An outcome as a single line of JSON.
+pub fn json(outcome: &Outcome) -> String {
+	let (status, answer, error) = match &outcome.status {
+		Status::Solved(answer) => ("solved", Some(answer.clone()), None),
+		Status::Failed(error) => ("failed", None, Some(error.clone())),
+		Status::Panicked(message) => ("panicked", None, Some(message.clone())),
+		Status::TimedOut(_) => ("timed_out", None, Some(outcome.status.to_string())),
+	};
+	json!({
+		"year": outcome.year,
+		"day": outcome.day,
+		"part": outcome.part.to_string().parse::<u8>().unwrap(),
+		"status": status,
+		"answer": answer,
+		"error": error,
+		"parse_ns": outcome.parse.as_nanos() as u64,
+		"solve_ns": outcome.solve.as_nanos() as u64,
+		"peak_bytes": outcome.memory,
+	}).to_string()
+}
+
+///While this is alive, anything written to stdout goes to stderr instead, so debug prints from
+///solutions can't end up in the middle of the JSON. Keep it for the whole run, since a part that
+///timed out may still be printing; the JSON itself goes through [`StdoutToStderr::println`].
+#[cfg(unix)]
+pub struct StdoutToStderr {
+	saved: std::fs::File,
+}
+
+#[cfg(unix)]
+impl StdoutToStderr {
+	pub fn new() -> std::io::Result<StdoutToStderr> {
+		use std::os::fd::AsFd;
+		std::io::stdout().flush()?;
+		let saved = std::fs::File::from(std::io::stdout().as_fd().try_clone_to_owned()?);
+		// SAFETY: dup2 only swaps which file descriptor 1 refers to; `saved` keeps the original open.
+		if unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } < 0 {
+			return Err(std::io::Error::last_os_error());
+		}
+		Ok(StdoutToStderr { saved })
+	}
+
+	///Writes a line to the real stdout.
+	pub fn println(&self, line: &str) -> std::io::Result<()> {
+		(&self.saved).write_all(format!("{line}\n").as_bytes())
+	}
+}
+
+#[cfg(unix)]
+impl Drop for StdoutToStderr {
+	fn drop(&mut self) {
+		use std::os::fd::AsRawFd;
+		let _ = std::io::stdout().flush();
+		// SAFETY: as above, `saved` is a valid descriptor for the original stdout.
+		unsafe { libc::dup2(self.saved.as_raw_fd(), libc::STDOUT_FILENO) };
+	}
+}
+
+///Elsewhere there's no redirecting file descriptors, so solutions' prints stay on stdout.
+#[cfg(not(unix))]
+pub struct StdoutToStderr;
+
+#[cfg(not(unix))]
+impl StdoutToStderr {
+	pub fn new() -> std::io::Result<StdoutToStderr> {
+		Ok(StdoutToStderr)
+	}
+
+	pub fn println(&self, line: &str) -> std::io::Result<()> {
+		let mut stdout = std::io::stdout().lock();
+		writeln!(stdout, "{line}")?;
+		stdout.flush()
+	}
+}
@@ -0,0 +1,162 @@
+//! Runs solutions and collects what happened
+
+use std::{panic::{catch_unwind, AssertUnwindSafe}, sync::{atomic::{AtomicBool, AtomicU64, Ordering}, mpsc, Arc}, thread, time::{Duration, Instant}};
+
+use crate::lib::{ memory, prelude::*, timing::take_parse_time };
+use crate::years::Day;
+
+///A day that is ready to run: which parts, on what input, and with which parameters.
+pub struct Job {
+	pub day: &'static Day,
+	pub parts: Vec<Part>,
+	pub input: String,
+	pub ctx: Context,
+	///Whether benchmarks of this job are compared against and saved as baselines, which are only
+	///meant for the downloaded input.
+	pub baseline: bool,
+}
+
+///How a single run of a part ended.
+#[derive(Debug, Clone, PartialEq, Eq, EnumIs)]
+pub enum Status {
+	Solved(String),
+	Failed(String),
+	Panicked(String),
+	///No answer came back within the time limit. The solution is left running in the background.
+	TimedOut(Duration),
+}
+
+impl Display for Status {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Status::Solved(answer) => write!(f, "{answer}"),
+			Status::Failed(error) => write!(f, "error: {error}"),
+			Status::Panicked(message) => write!(f, "panicked: {message}"),
+			Status::TimedOut(limit) => write!(f, "timed out after {limit:.0?}"),
+		}
+	}
+}
+
+///The result of running one part of one day.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+	pub year: usize,
+	pub day: usize,
+	pub part: Part,
+	pub status: Status,
+	///Time spent inside [`parsing`] blocks.
+	pub parse: Duration,
+	///Time spent on everything else.
+	pub solve: Duration,
+	///How much more heap was in use at the peak than when the part started, in bytes. Unknown if a
+	///part that timed out earlier was still running alongside it.
+	pub memory: Option<usize>,
+}
+
+impl Outcome {
+	///Time for the whole run.
+	pub fn total(&self) -> Duration {
+		self.parse + self.solve
+	}
+}
+
+///Gets the message out of a panic payload, which is usually a `&str` or a `String`.
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+	if let Some(message) = payload.downcast_ref::<&str>() {
+		message.to_string()
+	} else if let Some(message) = payload.downcast_ref::<String>() {
+		message.clone()
+	} else {
+		String::from("(non-string panic payload)")
+	}
+}
+
+///How long a part may run before it's given up on, in milliseconds. Zero means no limit.
+static TIMEOUT: AtomicU64 = AtomicU64::new(60_000);
+
+///Solutions get as much stack as they would on the main thread.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+pub fn set_timeout(limit: Option<Duration>) {
+	TIMEOUT.store(limit.map_or(0, |limit| limit.as_millis().max(1) as u64), Ordering::Relaxed);
+}
+
+pub fn timeout() -> Option<Duration> {
+	Some(TIMEOUT.load(Ordering::Relaxed)).filter(|&ms| ms > 0).map(Duration::from_millis)
+}
+
+///Runs one part of a day on its own thread, catching any panic instead of letting it take down the
+///runner, and giving up once [`timeout`] has passed. A part that times out can't be stopped, so it
+///keeps running (and using a core) until the program exits. Panics if the part has not been
+///written; check [`Day::parts`] first.
+pub fn run(day: &Day, part: Part, input: String, ctx: &Context) -> Outcome {
+	let solve = day.solver(part).expect("part has no solver");
+	let ctx = ctx.clone();
+	let (sender, receiver) = mpsc::channel();
+	// Set by whichever of the runner giving up and the solution finishing happens first, so exactly
+	// one side knows the part was abandoned.
+	let claimed = Arc::new(AtomicBool::new(false));
+	let solution_claimed = Arc::clone(&claimed);
+	let baseline = memory::start(day.year, day.day, part);
+	thread::Builder::new()
+		.name(format!("{} day {} part {part}", day.year, day.day))
+		.stack_size(STACK_SIZE)
+		.spawn(move || {
+			let start = Instant::now();
+			let result = catch_unwind(AssertUnwindSafe(|| solve(input, &ctx)));
+			let time = start.elapsed();
+			if solution_claimed.swap(true, Ordering::SeqCst) {
+				// The runner already gave up on this part.
+				memory::abandoned_finished();
+			} else {
+				let _ = sender.send((result, time, take_parse_time()));
+			}
+		})
+		.expect("could not start a thread for the solution");
+
+	// Waiting for `Duration::MAX` is the same as waiting forever.
+	let limit = timeout().unwrap_or(Duration::MAX);
+	let received = receiver.recv_timeout(limit).ok().or_else(|| {
+		// Counted first, so the solution can't take it back before it's been added.
+		memory::abandoned();
+		if claimed.swap(true, Ordering::SeqCst) {
+			// It finished just as time ran out, so its result is on the way.
+			memory::abandoned_finished();
+			receiver.recv().ok()
+		} else {
+			None
+		}
+	});
+	let (status, parse, time) = match received {
+		Some((Ok(Ok(answer)), time, parse)) => (Status::Solved(answer), parse, time),
+		Some((Ok(Err(error)), time, parse)) => (Status::Failed(format!("{error:#}")), parse, time),
+		Some((Err(payload), time, parse)) => (Status::Panicked(panic_message(payload)), parse, time),
+		None => (Status::TimedOut(limit), Duration::ZERO, limit),
+	};
+	Outcome { year: day.year, day: day.day, part, status, parse, solve: time.saturating_sub(parse), memory: memory::finish(baseline) }
+}
+
+///A column for peak memory, if it's being shown.
+fn memory_column(show: bool, text: &str) -> String {
+	if show { format!("{text:>10}  ") } else { String::new() }
+}
+
+///Peak memory as shown to people, or `?` if it couldn't be measured.
+pub fn format_memory(memory: Option<usize>) -> String {
+	memory.map_or(String::from("?"), memory::format_size)
+}
+
+///Prints the header of a table of outcomes, with a column for peak memory if `memory` is set.
+pub fn print_header(memory: bool) {
+	println!("{:<4}  {:>3}  {:>4}  {:>10}  {:>10}  {}Answer", "Year", "Day", "Part", "Parse", "Solve", memory_column(memory, "Memory"));
+}
+
+///Prints one row of the table, flushed straight away.
+pub fn print_row(outcome: &Outcome, memory: bool) {
+	let Outcome { year, day, part, status, parse, solve, .. } = outcome;
+	println!(
+		"{year:<4}  {day:>3}  {part:>4}  {:>10}  {:>10}  {}{status}",
+		format!("{parse:.2?}"), format!("{solve:.2?}"), memory_column(memory, &format_memory(outcome.memory))
+	);
+	let _ = std::io::Write::flush(&mut std::io::stdout());
+}
@@ -0,0 +1,37 @@
+//! Creating the files for a new day
+
+use std::fs;
+
+use crate::lib::{ input, prelude::* };
+
+///What a new day starts out as.
+const TEMPLATE: &str = include_str!("../sample_day.rs");
+
+///Creates `src/years/yearYYYY/dayN.rs` from the template, along with an empty sample to paste
+///into. Then tries to download the input, which is fine to fail if the puzzle hasn't unlocked yet.
+pub fn new_day(year: usize, day: usize) -> Result<()> {
+	input::check_day(year, day)?;
+	let year_dir = format!("src/years/year{year}");
+	let day_path = format!("{year_dir}/day{day}.rs");
+	if fs::exists(&day_path)? {
+		bail!("{day_path} already exists");
+	}
+
+	// build.rs makes the module for the year, so the directory is all it needs.
+	fs::create_dir_all(&year_dir)?;
+	fs::write(&day_path, TEMPLATE)?;
+	println!("Created {day_path}");
+
+	let sample_path = input::sample_path(year, day, 1);
+	if !fs::exists(&sample_path)? {
+		fs::create_dir_all(format!("input/{year}"))?;
+		fs::write(&sample_path, "")?;
+		println!("Created {sample_path}");
+	}
+
+	match input::input(year, day) {
+		Ok(_) => println!("Downloaded the input"),
+		Err(e) => eprintln!("Could not download the input yet: {e}"),
+	}
+	Ok(())
+}
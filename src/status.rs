@@ -0,0 +1,46 @@
+//! An overview of how far along each year is
+
+use crate::answers::load_answers;
+use crate::lib::{ calendar, input, prelude::* };
+use crate::years;
+
+///Marks for zero, one and two parts.
+fn parts_mark(count: usize) -> &'static str {
+	["  .", "  *", " **"][count]
+}
+
+///Prints a grid per year with a column for each day, showing which parts have been written, which
+///have an accepted answer, how many samples there are and whether the input has been downloaded.
+///Without a year, only years with anything in them are shown.
+pub fn status(only: Option<usize>) -> Result<()> {
+	if let Some(year) = only {
+		input::check_year(year)?;
+	}
+	let mut shown = false;
+	for year in only.map_or(calendar::FIRST_YEAR..=calendar::latest_year(), |y| y..=y) {
+		let answers = load_answers(year)?;
+		let days = 1..=calendar::days_in(year);
+
+		let written: Vec<usize> = days.clone().map(|day| years::find(year, day).map_or(0, |d| d.parts().len())).collect();
+		let stars: Vec<usize> = days.clone().map(|day| [Part::One, Part::Two].into_iter().filter(|&p| answers.get(day, p).is_some()).count()).collect();
+		let samples: Vec<usize> = days.clone().map(|day| input::samples(year, day).len()).collect();
+		let inputs: Vec<bool> = days.clone().map(|day| input::input_path(year, day).exists()).collect();
+
+		let empty = written.iter().chain(&stars).chain(&samples).all(|&n| n == 0) && !inputs.contains(&true);
+		if empty && only.is_none() {
+			continue;
+		}
+		shown = true;
+
+		println!("{year}   {}", days.clone().map(|day| format!("{day:>3}")).collect::<String>());
+		println!("Code   {}", written.iter().map(|&n| parts_mark(n)).collect::<String>());
+		println!("Stars  {}", stars.iter().map(|&n| parts_mark(n)).collect::<String>());
+		println!("Sample {}", samples.iter().map(|&n| if n == 0 { String::from("  .") } else { format!("{n:>3}") }).collect::<String>());
+		println!("Input  {}", inputs.iter().map(|&cached| if cached { "  +" } else { "  ." }).collect::<String>());
+		println!("{}/{} stars\n", stars.iter().sum::<usize>(), 2 * calendar::days_in(year));
+	}
+	if !shown {
+		println!("Nothing has been started yet.");
+	}
+	Ok(())
+}
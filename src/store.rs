@@ -0,0 +1,42 @@
+//! Per-year TOML files holding one value for each part of each day
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use serde::{ de::DeserializeOwned, Serialize };
+
+use crate::lib::prelude::*;
+
+///A table stored as `[dayN] partP = value`, loaded from and saved to a single file.
+#[derive(Debug)]
+pub struct PartStore<T> {
+	path: PathBuf,
+	days: BTreeMap<String, BTreeMap<String, T>>,
+}
+
+impl<T: Serialize + DeserializeOwned> PartStore<T> {
+	///Loads a store, or starts an empty one if the file does not exist yet.
+	pub fn load(path: PathBuf) -> Result<PartStore<T>> {
+		let days = match fs::read_to_string(&path) {
+			Ok(text) => toml::from_str(&text).map_err(|e| anyhow!("{}: {e}", path.display()))?,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+			Err(e) => return Err(e.into()),
+		};
+		Ok(PartStore { path, days })
+	}
+
+	pub fn save(&self) -> Result<()> {
+		if let Some(parent) = self.path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		fs::write(&self.path, toml::to_string(&self.days)?)?;
+		Ok(())
+	}
+
+	pub fn get(&self, day: usize, part: Part) -> Option<&T> {
+		self.days.get(&format!("day{day}"))?.get(&format!("part{part}"))
+	}
+
+	pub fn set(&mut self, day: usize, part: Part, value: T) {
+		self.days.entry(format!("day{day}")).or_default().insert(format!("part{part}"), value);
+	}
+}
@@ -0,0 +1,145 @@
+//! Rebuilding and rerunning a day whenever its files change
+
+use std::{fs, path::PathBuf, process, thread, time::{Duration, SystemTime}};
+
+use crate::lib::{ config, input, log, memory, prelude::* };
+use crate::runner::{ self, Status };
+
+///How often the watched files are checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+///When each watched file was last modified, or `None` if it doesn't exist (yet).
+fn modified(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+	paths.iter().map(|path| fs::metadata(path).and_then(|m| m.modified()).ok()).collect()
+}
+
+///How rebuilding and rerunning a day went.
+enum Attempt {
+	BuildFailed,
+	///The program stopped with an error before reporting any part, which it printed itself.
+	RunFailed,
+	Finished(Vec<(Part, Status)>),
+}
+
+///Cargo, with the same profile as the watcher itself.
+fn cargo(subcommand: &str) -> process::Command {
+	let mut command = process::Command::new(std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo")));
+	command.args([subcommand, "--quiet"]);
+	if !cfg!(debug_assertions) {
+		command.arg("--release");
+	}
+	command
+}
+
+///Rebuilds with cargo and runs the day in a child process, collecting each part's status from its
+///JSON output. Build errors and whatever the solution logs go straight to the terminal.
+fn run_once(year: usize, day: usize, part: Option<Part>, sample: Option<usize>) -> Result<Attempt> {
+	// Built separately, so a failed build can be told apart from the program failing.
+	if !cargo("build").status()?.success() {
+		return Ok(Attempt::BuildFailed);
+	}
+
+	let mut command = cargo("run");
+	command.args(["--", &year.to_string(), &day.to_string(), "--format", "json"]);
+	if let Some(part) = part {
+		command.args(["--part", &part.to_string()]);
+	}
+	if let Some(number) = sample {
+		command.args(["--sample", &number.to_string()]);
+	}
+	if let Some(profile) = config::profile() {
+		command.args(["--profile", &profile]);
+	}
+	if let Some(limit) = memory::limit() {
+		command.args(["--max-memory", &limit.to_string()]);
+	}
+	command.args(["--timeout", &runner::timeout().map_or(0, |limit| limit.as_secs()).to_string()]);
+	// JSON output starts out quiet, so this brings the child up to the watcher's own level.
+	for _ in 0..log::level() as usize {
+		command.arg("-v");
+	}
+
+	let output = command.stderr(process::Stdio::inherit()).output()?;
+	let mut statuses = vec![];
+	for line in String::from_utf8_lossy(&output.stdout).lines() {
+		let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else { continue };
+		let Some(part) = value["part"].as_u64().and_then(|p| p.to_string().parse().ok()) else { continue };
+		let text = |key: &str| value[key].as_str().unwrap_or_default().to_string();
+		let status = match value["status"].as_str() {
+			Some("solved") => Status::Solved(text("answer")),
+			Some("panicked") => Status::Panicked(text("error")),
+			Some("timed_out") => Status::TimedOut(Duration::from_nanos(value["solve_ns"].as_u64().unwrap_or_default())),
+			_ => Status::Failed(text("error")),
+		};
+		statuses.push((part, status));
+	}
+
+	if statuses.is_empty() && !output.status.success() {
+		return Ok(Attempt::RunFailed);
+	}
+	Ok(Attempt::Finished(statuses))
+}
+
+///Watches a day's source along with its sample or input, and reruns it whenever one of them is
+///saved. Each answer is shown next to the one from the previous run and the sample's expected answer.
+pub fn watch(year: usize, day: usize, part: Option<Part>, sample: Option<usize>) -> Result<()> {
+	input::check_day(year, day)?;
+	let source = PathBuf::from(format!("src/years/year{year}/day{day}.rs"));
+	if !source.exists() {
+		bail!("{} does not exist. Create it with `new {year} {day}`.", source.display());
+	}
+	let data = match sample {
+		Some(number) => PathBuf::from(input::sample_path(year, day, number)),
+		None => input::input_path(year, day),
+	};
+	let paths = [source, data];
+
+	let mut previous: Vec<(Part, Status)> = vec![];
+	let mut seen = modified(&paths);
+	loop {
+		println!("Running {year} day {day}{}...", sample.map_or(String::new(), |n| format!(" on sample {n}")));
+		match run_once(year, day, part, sample)? {
+			Attempt::BuildFailed => println!("Build failed"),
+			Attempt::RunFailed => println!("Run failed, see the error above"),
+			Attempt::Finished(statuses) => {
+				// Read again every time, since the expected answers may have just been filled in.
+				let expected = match sample.map(|n| input::sample(year, day, n)).transpose() {
+					Ok(sample) => sample,
+					Err(e) => {
+						eprintln!("{e}");
+						None
+					},
+				};
+				for (part, status) in &statuses {
+					let mut notes = vec![];
+					match previous.iter().find(|(p, _)| p == part) {
+						Some((_, before)) if before != status => notes.push(format!("was {before}")),
+						Some(_) => notes.push(String::from("unchanged")),
+						None => {},
+					}
+					if let Some(answer) = expected.as_ref().and_then(|s| s.expected(*part)) {
+						match status {
+							Status::Solved(solved) if solved == answer => notes.push(String::from("matches the sample")),
+							_ => notes.push(format!("sample expects {answer}")),
+						}
+					}
+					let notes = if notes.is_empty() { String::new() } else { format!("  ({})", notes.join(", ")) };
+					println!("Part {part}: {status}{notes}");
+				}
+				previous = statuses;
+			},
+		}
+		println!("Waiting for changes to {}...", paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>().join(" or "));
+
+		loop {
+			thread::sleep(POLL_INTERVAL);
+			let now = modified(&paths);
+			if now != seen {
+				// Editors often write a file in several steps, so wait for it to settle.
+				thread::sleep(POLL_INTERVAL);
+				seen = modified(&paths);
+				break;
+			}
+		}
+	}
+}
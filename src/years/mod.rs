@@ -1,12 +1,66 @@
-include!("generated_list.rs");
+use std::collections::BTreeMap;
 
-#[cfg(feature = "default")]
-mod year {
-	pub fn puzzle(_: String) -> anyhow::Result<String> {
-		unimplemented!("Do not use default features!");
+use crate::lib::{ input::Sample, prelude::{ Context, Param, Part, Result, bail } };
+
+///Signature shared by every part's entry point.
+pub type Solver = fn(String, &Context) -> Result<String>;
+
+///A day whose solution was found in `src/years` by build.rs.
+pub struct Day {
+	pub year: usize,
+	pub day: usize,
+	pub part1: Option<Solver>,
+	pub part2: Option<Solver>,
+	///The day's `PARAMS`, or nothing if it does not declare any.
+	pub params: &'static [Param],
+}
+
+impl Day {
+	///Gets the function that solves a part, or None if it has not been written yet.
+	pub fn solver(&self, part: Part) -> Option<Solver> {
+		match part {
+			Part::One => self.part1,
+			Part::Two => self.part2,
+		}
+	}
+	///Lists the parts that have been written.
+	pub fn parts(&self) -> Vec<Part> {
+		[Part::One, Part::Two].into_iter().filter(|p| self.solver(*p).is_some()).collect()
+	}
+	///Builds the context for a run, taking parameters from the declared defaults, then the sample's
+	///front matter, then `overrides`.
+	pub fn context(&self, sample: Option<&Sample>, overrides: &[(String, String)]) -> Result<Context> {
+		let mut params: BTreeMap<String, String> = self.params.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+		let sample_params = sample.into_iter().flat_map(|s| &s.params);
+		for (name, value) in sample_params.chain(overrides.iter().map(|(name, value)| (name, value))) {
+			let Some(param) = params.get_mut(name) else {
+				bail!("{} day {} has no parameter named {name}", self.year, self.day);
+			};
+			param.clone_from(value);
+		}
+		Ok(Context { sample: sample.is_some(), params })
 	}
 }
 
-pub fn puzzle(input: String) -> anyhow::Result<String> {
-	year::puzzle(input)
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+///Looks up a day in the list generated by build.rs.
+pub fn find(year: usize, day: usize) -> Option<&'static Day> {
+	DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	///Runs one part of a day against a sample and compares it to the answer in the sample's front matter.
+	fn check_sample(year: usize, day: usize, number: usize, part: Part) {
+		let sample = crate::lib::input::sample(year, day, number).unwrap();
+		let day = find(year, day).unwrap();
+		let ctx = day.context(Some(&sample), &[]).unwrap();
+		let answer = day.solver(part).unwrap()(sample.input.clone(), &ctx).unwrap();
+		assert_eq!(Some(answer.as_str()), sample.expected(part));
+	}
+
+	include!(concat!(env!("OUT_DIR"), "/sample_tests.rs"));
 }
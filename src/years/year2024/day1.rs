@@ -1,6 +1,6 @@
 use crate::lib::prelude::*;
 
-pub fn main(input: String) -> Result<String> {
+fn parse(input: &str) -> Result<(Vec<u32>, Vec<u32>)> {
 	let mut left_list: Vec<u32> = vec![];
 	let mut right_list: Vec<u32> = vec![];
 	for line in input.lines() {
@@ -12,28 +12,30 @@ pub fn main(input: String) -> Result<String> {
 		right_list.push(right.parse()?);
 	}
 
-	if !cfg!(feature = "part2") {
-
-		left_list.sort();
-		right_list.sort();
-		let mut sum = 0;
+	Ok((left_list, right_list))
+}
 
-		for (left, right) in left_list.iter().zip(right_list.iter()) {
-			sum += left.abs_diff(*right);
-		}
-		Ok(format!("{sum}"))
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let (mut left_list, mut right_list) = parsing(|| parse(&input))?;
+	left_list.sort();
+	right_list.sort();
+	let mut sum = 0;
 
-	} else {
+	for (left, right) in left_list.iter().zip(right_list.iter()) {
+		sum += left.abs_diff(*right);
+	}
+	Ok(format!("{sum}"))
+}
 
-		let mut sum = 0;
-		for num in &left_list {
-			for num2 in &right_list {
-				if num2 == num {
-					sum += num;
-				}
+pub fn part2(input: String, _: &Context) -> Result<String> {
+	let (left_list, right_list) = parsing(|| parse(&input))?;
+	let mut sum = 0;
+	for num in &left_list {
+		for num2 in &right_list {
+			if num2 == num {
+				sum += num;
 			}
 		}
-		Ok(format!("{sum}"))
-
 	}
+	Ok(format!("{sum}"))
 }
@@ -14,11 +14,11 @@ struct StoneCounter {
 		if iters == 0 { return 1 };
 		if input == 0 { return self.stone_count(1, iters-1) };
 		let stringified = input.to_string();
-		if stringified.len() % 2 == 0 {
+		if stringified.len().is_multiple_of(2) {
 			let (left, right) = stringified.split_at(stringified.len() / 2);
-			return self.stone_count(left.parse().unwrap(), iters-1) + self.stone_count(right.parse().unwrap(), iters-1);
+			self.stone_count(left.parse().unwrap(), iters-1) + self.stone_count(right.parse().unwrap(), iters-1)
 		} else {
-			return self.stone_count(input * 2024, iters-1);
+			self.stone_count(input * 2024, iters-1)
 		}
 	}
 	fn stone_count(&mut self, input: u64, iters: usize) -> usize {
@@ -34,11 +34,27 @@ struct StoneCounter {
 }
 
 
-pub fn main(input: String) -> Result<String> {
+pub const PARAMS: &[Param] = &[("part1_blinks", "25"), ("part2_blinks", "75")];
+
+fn parse(input: &str) -> Vec<u64> {
+	input.split_whitespace().map(|word| word.parse().unwrap()).collect()
+}
+
+fn count_stones(stones: &[u64], blinks: usize) -> usize {
 	let mut total = 0;
 	let mut counter = StoneCounter::default();
-	for word in input.split_whitespace() {
-		total += counter.stone_count(word.parse().unwrap(), if cfg!(feature = "part2") { 75 } else { 25 });
+	for &stone in stones {
+		total += counter.stone_count(stone, blinks);
 	}
-	Ok(format!("{total}"))
+	total
+}
+
+pub fn part1(input: String, ctx: &Context) -> Result<String> {
+	let stones = parsing(|| parse(&input));
+	Ok(format!("{}", count_stones(&stones, ctx.param("part1_blinks")?)))
+}
+
+pub fn part2(input: String, ctx: &Context) -> Result<String> {
+	let stones = parsing(|| parse(&input));
+	Ok(format!("{}", count_stones(&stones, ctx.param("part2_blinks")?)))
 }
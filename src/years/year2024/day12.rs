@@ -45,14 +45,13 @@ fn compute_area_and_perim((ch, x, y): (char, usize, usize), grid: &mut impl Grid
 	(area, perim)
 }
 
-pub fn main(input: String) -> Result<String> {
-	let mut grid = ItemGrid::<Cell>::from(&input[..]);
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let mut grid = parsing(|| ItemGrid::<Cell>::from(&input[..]));
 	let mut sum = 0;
-	loop {
-		let Some((start_cell, start_x, start_y)) = grid.find(|c, _, _| c.visited == false) else { break };
+	while let Some((start_cell, start_x, start_y)) = grid.find(|c, _, _| !c.visited) {
 		let ch = start_cell.ch;
 		let (area, perimeter) = compute_area_and_perim((ch, start_x, start_y), &mut grid);
-		println!("{ch}: {area} * {perimeter} = {}", area * perimeter);
+		debug!("{ch}: {area} * {perimeter} = {}", area * perimeter);
 		sum += area * perimeter;
 	}
 
@@ -13,20 +13,7 @@ impl Display for Robot {
 	}
 }
 
-#[cfg(feature = "sample")]
-const WIDTH: isize = 11;
-#[cfg(feature = "sample")]
-const HEIGHT: isize = 7;
-
-#[cfg(not(feature = "sample"))]
-const WIDTH: isize = 101;
-#[cfg(not(feature = "sample"))]
-const HEIGHT: isize = 103;
-
-const WIDTH_U: usize = WIDTH as usize;
-const HEIGHT_U: usize = HEIGHT as usize;
-
-pub fn main(input: String) -> Result<String> {
+fn parse(input: &str) -> Vec<Robot> {
 	let mut robots = vec![];
 	for line in input.lines() {
 		let (pos_part, vel_part) = line.split_once(' ').unwrap();
@@ -42,57 +29,69 @@ pub fn main(input: String) -> Result<String> {
 			velocity: (vel_x.parse().unwrap(), vel_y.parse().unwrap()),
 		});
 	}
-	if cfg!(not(feature = "part2")) {
-		for _ in 0..100 {
-			for robot in &mut robots {
-				robot.position.0 += robot.velocity.0;
-				robot.position.1 += robot.velocity.1;
-				while robot.position.0 >= WIDTH  { robot.position.0 -= WIDTH  };
-				while robot.position.0 < 0       { robot.position.0 += WIDTH  };
-				while robot.position.1 >= HEIGHT { robot.position.1 -= HEIGHT };
-				while robot.position.1 < 0       { robot.position.1 += HEIGHT };
-			}
+	robots
+}
+
+///The size of the room the robots are in, which is smaller in the sample.
+pub const PARAMS: &[Param] = &[("width", "101"), ("height", "103")];
+
+pub fn part1(input: String, ctx: &Context) -> Result<String> {
+	let (width, height): (isize, isize) = (ctx.param("width")?, ctx.param("height")?);
+	let mut robots = parsing(|| parse(&input));
+	for _ in 0..100 {
+		for robot in &mut robots {
+			robot.position.0 += robot.velocity.0;
+			robot.position.1 += robot.velocity.1;
+			while robot.position.0 >= width  { robot.position.0 -= width  };
+			while robot.position.0 < 0       { robot.position.0 += width  };
+			while robot.position.1 >= height { robot.position.1 -= height };
+			while robot.position.1 < 0       { robot.position.1 += height };
 		}
-		let mut quadrants = [0; 4];
-		for robot in &robots {
-			if robot.position.0 < WIDTH/2 && robot.position.1 < HEIGHT/2 {
-				quadrants[0] += 1;
-			}
-			if robot.position.0 > WIDTH/2 && robot.position.1 < HEIGHT/2 {
-				quadrants[1] += 1;
-			}
-			if robot.position.0 < WIDTH/2 && robot.position.1 > HEIGHT/2 {
-				quadrants[2] += 1;
-			}
-			if robot.position.0 > WIDTH/2 && robot.position.1 > HEIGHT/2 {
-				quadrants[3] += 1;
-			}
+	}
+	let mut quadrants = [0; 4];
+	for robot in &robots {
+		if robot.position.0 < width/2 && robot.position.1 < height/2 {
+			quadrants[0] += 1;
 		}
-		Ok(format!("{}", quadrants[0] * quadrants[1] * quadrants[2] * quadrants[3]))
-	} else { //In the easter egg pattern, each robot is on its own cell.
-		let mut i = 0;
-		'step: loop {
-			for robot in &mut robots {
-				robot.position.0 += robot.velocity.0;
-				robot.position.1 += robot.velocity.1;
-				while robot.position.0 >= WIDTH  { robot.position.0 -= WIDTH  };
-				while robot.position.0 < 0       { robot.position.0 += WIDTH  };
-				while robot.position.1 >= HEIGHT { robot.position.1 -= HEIGHT };
-				while robot.position.1 < 0       { robot.position.1 += HEIGHT };
-			}
+		if robot.position.0 > width/2 && robot.position.1 < height/2 {
+			quadrants[1] += 1;
+		}
+		if robot.position.0 < width/2 && robot.position.1 > height/2 {
+			quadrants[2] += 1;
+		}
+		if robot.position.0 > width/2 && robot.position.1 > height/2 {
+			quadrants[3] += 1;
+		}
+	}
+	Ok(format!("{}", quadrants[0] * quadrants[1] * quadrants[2] * quadrants[3]))
+}
 
-			i += 1;
-			println!("{i}");
-			
-			let mut g = ConstSizeGrid::<WIDTH_U, HEIGHT_U, bool>::filled_with(false);
-			for robot in &robots {
-				if *g.get(robot.position.0 as usize, robot.position.1 as usize) {
-					continue 'step;
-				}
-				g.set(robot.position.0 as usize, robot.position.1 as usize, true);
+//In the easter egg pattern, each robot is on its own cell.
+pub fn part2(input: String, ctx: &Context) -> Result<String> {
+	let (width, height): (isize, isize) = (ctx.param("width")?, ctx.param("height")?);
+	let mut robots = parsing(|| parse(&input));
+	let mut i = 0;
+	'step: loop {
+		for robot in &mut robots {
+			robot.position.0 += robot.velocity.0;
+			robot.position.1 += robot.velocity.1;
+			while robot.position.0 >= width  { robot.position.0 -= width  };
+			while robot.position.0 < 0       { robot.position.0 += width  };
+			while robot.position.1 >= height { robot.position.1 -= height };
+			while robot.position.1 < 0       { robot.position.1 += height };
+		}
+
+		i += 1;
+		trace!("{i}");
+		
+		let mut g = ItemGrid::new_with_size(width as usize, height as usize, false);
+		for robot in &robots {
+			if *g.get(robot.position.0 as usize, robot.position.1 as usize) {
+				continue 'step;
 			}
-			// If we didn't go to the next step, there were no collisions.
-			return Ok(format!("{i}"));
+			g.set(robot.position.0 as usize, robot.position.1 as usize, true);
 		}
+		// If we didn't go to the next step, there were no collisions.
+		return Ok(format!("{i}"));
 	}
 }
@@ -40,27 +40,32 @@ fn push_box(x: usize, y: usize, ox: isize, oy: isize, grid: &mut ItemGrid<Cell>)
 	should_push
 }
 
-pub fn main(input: String) -> Result<String> {
+///Reads the warehouse and the robot's moves, as offsets.
+fn parse(input: &str) -> (ItemGrid<Cell>, Vec<(isize, isize)>) {
 	let (grid_text, moves_text) = input.split_once("\n\n").unwrap();
-	let mut grid = ItemGrid::<Cell>::from(grid_text.trim());
+	let grid = ItemGrid::<Cell>::from(grid_text.trim());
+	let moves = moves_text.trim().chars().filter(|&ch| ch != '\n').map(|ch| match ch {
+		'^' => ( 0, -1),
+		'<' => (-1,  0),
+		'>' => ( 1,  0),
+		'v' => ( 0,  1),
+		ch => unimplemented!("{ch:?} move"),
+	}).collect();
+	(grid, moves)
+}
+
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let (mut grid, moves) = parsing(|| parse(&input));
 	let (_, mut x, mut y) = grid.find(|c,_,_| c.is_robot()).unwrap();
 
-	for ch in moves_text.trim().chars() {
-		let (ox, oy) = match ch {
-			'^' => ( 0, -1),
-			'<' => (-1,  0),
-			'>' => ( 1,  0),
-			'v' => ( 0,  1),
-			'\n' => continue,
-			ch => unimplemented!("{ch:?} move"),
-		};
+	for (ox, oy) in moves {
 		if push_box(x, y, ox, oy, &mut grid) {
 			grid.set(x, y, Cell::None);
 			x = x.saturating_add_signed(ox);
 			y = y.saturating_add_signed(oy);
 			grid.set(x, y, Cell::Robot);
 		}
-		// println!("Move {ch}:\n{grid}");
+		// println!("Move {ox},{oy}:\n{grid}");
 	}
 
 	let mut sum = 0;
@@ -1,16 +1,16 @@
-use std::{num::NonZero, sync::{atomic::AtomicBool, mpsc::TryRecvError, Arc}};
+use std::{num::NonZero, sync::atomic::{AtomicI64, Ordering}};
 
 use crate::lib::prelude::*;
 
 #[derive(Default, Debug, Clone)]
-struct CPU {
+struct Cpu {
 	a: i64,
 	b: i64,
 	c: i64,
 	pc: usize,
 	program: Vec<u8>,
 	output: Vec<u8>,
-} impl CPU {
+} impl Cpu {
 	fn tick(&mut self, show: bool) {
 		let opcode = self.program[self.pc];
 		let literal_operand = self.program[self.pc+1];
@@ -31,12 +31,12 @@ struct CPU {
 			/* adv */ 0 => { //Divide A by 2**N and store in A
 				opcode_str = "adv";
 				source_is_combo = true;
-				self.a = self.a >> combo_operand
+				self.a >>= combo_operand
 			},
 			/* bxl */ 1 => { //XOR B by L and store in B
 				opcode_str = "bxl";
 				source_is_combo = false;
-				self.b = self.b ^ literal_operand as i64;
+				self.b ^= literal_operand as i64;
 			}
 			/* bst */ 2 => { //Modulo N by 8 and store in B
 				opcode_str = "bst";
@@ -51,7 +51,7 @@ struct CPU {
 			/* bxc */ 4 => { //XOR B by C and store in B
 				opcode_str = "bxc";
 				source_is_combo = true;
-				self.b = self.b ^ self.c;
+				self.b ^= self.c;
 			},
 			/* out */ 5 => { //Append N mod 8 to output
 				opcode_str = "out";
@@ -71,7 +71,7 @@ struct CPU {
 			c => unimplemented!("opcode {c:?}")
 		}
 
-		if show { println!(
+		if show { trace!(
 			"pc={:<2} a={old_a:<6} b={old_b:<6} c={old_c:<6} {opcode_str} {}",
 			self.pc, if source_is_combo {
 				match literal_operand {
@@ -87,69 +87,64 @@ struct CPU {
 	}
 }
 
-pub fn main(input: String) -> Result<String> {
-	let mut cpu = CPU::default();
+fn parse(input: &str) -> Cpu {
+	let mut cpu = Cpu::default();
 	for line in input.lines() {
-		if line.starts_with("Register A: ") {
-			cpu.a = line["Register A: ".len()..].parse().unwrap();
+		if let Some(value) = line.strip_prefix("Register A: ") {
+			cpu.a = value.parse().unwrap();
 		}
-		if line.starts_with("Register B: ") {
-			cpu.b = line["Register B: ".len()..].parse().unwrap();
+		if let Some(value) = line.strip_prefix("Register B: ") {
+			cpu.b = value.parse().unwrap();
 		}
-		if line.starts_with("Register C: ") {
-			cpu.c = line["Register C: ".len()..].parse().unwrap();
+		if let Some(value) = line.strip_prefix("Register C: ") {
+			cpu.c = value.parse().unwrap();
 		}
-		if line.starts_with("Program: ") {
-			cpu.program = line["Program: ".len()..].split(",").map(|v| v.parse().unwrap()).collect();
+		if let Some(program) = line.strip_prefix("Program: ") {
+			cpu.program = program.split(",").map(|v| v.parse().unwrap()).collect();
 		}
 	}
-	if cfg!(not(feature = "part2")) {
-		while cpu.pc < cpu.program.len() {
-			cpu.tick(true);
-		}
-		Ok(cpu.output.iter().map(u8::to_string).collect::<Vec<_>>().join(","))
-	} else {
-		let mut threads = vec![];
-		let mut is_going = Arc::new(AtomicBool::new(true));
+	cpu
+}
 
-		for n in 0..std::thread::available_parallelism().map(NonZero::get).unwrap_or(16) {
-			let thread_cpu = cpu.clone();
-			let (tx, rx) = std::sync::mpsc::channel::<i64>();
-			threads.push(rx);
-			let thread_is_going = Arc::clone(&is_going);
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let mut cpu = parsing(|| parse(&input));
+	while cpu.pc < cpu.program.len() {
+		cpu.tick(true);
+	}
+	Ok(cpu.output.iter().map(u8::to_string).collect::<Vec<_>>().join(","))
+}
+
+pub fn part2(input: String, _: &Context) -> Result<String> {
+	let cpu = parsing(|| parse(&input));
+	// The lowest A found so far. Each thread tries every `threads`th value, and gives up once it's
+	// past this, so every thread stops once the answer is known.
+	let best = AtomicI64::new(i64::MAX);
+	let threads = std::thread::available_parallelism().map(NonZero::get).unwrap_or(16);
 
-			std::thread::spawn(move || {
-				let mut a = 0;
-				loop {
+	std::thread::scope(|scope| {
+		for n in 0..threads {
+			let thread_cpu = cpu.clone();
+			let best = &best;
+			scope.spawn(move || {
+				let mut a = n as i64;
+				while a < best.load(Ordering::Relaxed) {
 					let mut instance = thread_cpu.clone();
 					instance.a = a;
 					while instance.pc < instance.program.len() {
 						instance.tick(false);
 					}
 					if instance.output == instance.program {
-						let Ok(_) = tx.send(a) else { break };
-					} else {
-						if !thread_is_going.load(std::sync::atomic::Ordering::Relaxed) {
-							break;
-						}
+						best.fetch_min(a, Ordering::Relaxed);
+						break;
 					}
-					a += n as i64;
+					a += threads as i64;
 				}
 			});
 		}
-		loop {
-			for rx in &mut threads {
-				match rx.try_recv() {
-					Ok(value) => {
-						println!("DONE! a={value}");
-						is_going.store(true, std::sync::atomic::Ordering::Relaxed);
-					},
-					Err(TryRecvError::Empty) => {},
-					Err(TryRecvError::Disconnected) => {
-						println!("A thread has disconnected.");
-					}
-				}
-			}
-		}
+	});
+
+	match best.into_inner() {
+		i64::MAX => bail!("no value of A makes the program output itself"),
+		value => Ok(value.to_string()),
 	}
 }
@@ -4,49 +4,59 @@ use crate::lib::prelude::*;
 
 pub fn test_safety(input: impl Iterator<Item = u8>) -> bool {
 	let mut safe = true;
+	// The report, with the level that makes it unsafe in red.
+	let mut shown = log::enabled(Level::Debug).then(String::new);
 	let mut last_num: Option<u8> = None;
 	let mut last_ord: Option<Ordering> = None;
 
 	for num in input {
 		if let Some(last_num) = last_num {
 			let diff = last_num.abs_diff(num);
-			if diff < 1 || diff > 3 {
+			if !(1..=3).contains(&diff) {
 				safe = false;
-				print!("\x1b[31m{num}\x1b[39m");
+				if let Some(shown) = &mut shown { shown.push_str(&format!("\x1b[31m{num}\x1b[39m")) };
 				break;
 			}
 
-			if let Some(last_ord) = last_ord {
-				if last_ord != num.cmp(&last_num) {
-					safe = false;
-					print!("\x1b[31m{num}\x1b[39m");
-					break;
-				}
+			if let Some(last_ord) = last_ord && last_ord != num.cmp(&last_num) {
+				safe = false;
+				if let Some(shown) = &mut shown { shown.push_str(&format!("\x1b[31m{num}\x1b[39m")) };
+				break;
 			}
 			last_ord = Some(num.cmp(&last_num));
 		}
 
-		print!("{num} ");
+		if let Some(shown) = &mut shown { shown.push_str(&format!("{num} ")) };
 		last_num = Some(num);
 	}
 
-	println!();
+	if let Some(shown) = shown { debug!("{shown}") };
 	safe
 }
 
-pub fn main(input: String) -> Result<String> {
-	let mut safe_reports = 0;
-
-	'test_report: for line in input.lines() {
+///Reads each report's levels.
+fn parse(input: &str) -> Result<Vec<Vec<u8>>> {
+	let mut reports = vec![];
+	for line in input.lines() {
 		let mut numbers: Vec<u8> = vec![];
 		for num in line.split_whitespace() {
 			numbers.push(num.parse()?);
 		}
+		reports.push(numbers);
+	}
+	Ok(reports)
+}
 
+///Counts reports that are safe. With `dampened`, a report that becomes safe after removing one
+///level is also counted.
+fn count_safe(reports: &[Vec<u8>], dampened: bool) -> usize {
+	let mut safe_reports = 0;
+
+	'test_report: for numbers in reports {
 		if test_safety(numbers.iter().copied()) {
 			safe_reports += 1;
 			continue;
-		} else if cfg!(feature = "part2") {
+		} else if dampened {
 			for exclude in 0..numbers.len() {
 				if test_safety(numbers.iter().enumerate().filter_map(|(i,n)| if i == exclude { None } else { Some(*n) })) {
 					safe_reports += 1;
@@ -56,5 +66,15 @@ pub fn main(input: String) -> Result<String> {
 		}
 	}
 
-	Ok(format!("{safe_reports}"))
+	safe_reports
+}
+
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let reports = parsing(|| parse(&input))?;
+	Ok(format!("{}", count_safe(&reports, false)))
+}
+
+pub fn part2(input: String, _: &Context) -> Result<String> {
+	let reports = parsing(|| parse(&input))?;
+	Ok(format!("{}", count_safe(&reports, true)))
 }
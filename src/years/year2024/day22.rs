@@ -1,7 +1,7 @@
 use crate::lib::prelude::*;
 
-struct RNG(u32);
-impl RNG {
+struct Rng(u32);
+impl Rng {
 	fn mix(&mut self, n: u32) {
 		self.0 ^= n;
 	}
@@ -18,9 +18,13 @@ impl RNG {
 	}
 }
 
-pub fn main(input: String) -> Result<String> {
-	let mut rngs = input.lines().map(|line| RNG(line.parse().unwrap())).collect::<Vec<_>>();
-	for i in 0..2000 {
+///How many times each buyer's secret number changes.
+pub const PARAMS: &[Param] = &[("iterations", "2000")];
+
+pub fn part1(input: String, ctx: &Context) -> Result<String> {
+	let iterations: usize = ctx.param("iterations")?;
+	let mut rngs = parsing(|| input.lines().map(|line| Rng(line.parse().unwrap())).collect::<Vec<_>>());
+	for _ in 0..iterations {
 		// println!("\x1b[HIteration: {i}\x1b[K");
 		for rng in &mut rngs {
 			rng.next();
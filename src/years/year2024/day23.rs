@@ -24,12 +24,13 @@ struct Computer {
 	connections: Vec<Ident>,
 }
 
-pub fn main(input: String) -> Result<String> {
+///Reads the network map into each computer's list of connections.
+fn parse(input: &str) -> HashMap<Ident, Computer> {
 	let mut computers = HashMap::new();
 	for line in input.lines() {
 		let (left, right) = line.split_once('-').unwrap();
-		let left: Ident = left.try_into().unwrap();
-		let right: Ident = right.try_into().unwrap();
+		let left: Ident = left.into();
+		let right: Ident = right.into();
 
 		match computers.get_mut(&left) {
 			None => {
@@ -55,6 +56,11 @@ pub fn main(input: String) -> Result<String> {
 			}
 		}
 	}
+	computers
+}
+
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let computers = parsing(|| parse(&input));
 
 	let mut connected: Vec<(Ident, Ident, Ident)> = vec![];
 	for (comp1_ident, comp1) in &computers {
@@ -87,6 +93,6 @@ pub fn main(input: String) -> Result<String> {
 			}
 		}
 	}
-	println!("{:#?}", connected.len());
-	Ok(format!(""))
+	debug!("{:#?}", connected.len());
+	Ok(String::new())
 }
@@ -12,7 +12,7 @@ impl From<char> for Cell {
 	fn from(value: char) -> Self {
 		match value {
 			'.' => Cell::Clear,
-			'#' | _ => Cell::Filled
+			_ => Cell::Filled
 		}
 	}
 }
@@ -22,13 +22,14 @@ struct Lock(Vec<usize>);
 #[derive(Debug)]
 struct Key(Vec<usize>);
 
-pub fn main(input: String) -> Result<String> {
+///Reads the schematics into locks and keys, along with how tall they all are.
+fn parse(input: &str) -> Result<(Vec<Lock>, Vec<Key>, Option<usize>)> {
 	let mut locks = vec![];
 	let mut keys = vec![];
 	let mut max_height = None;
 
 	for part in input.split("\n\n") {
-		let grid = ItemGrid::<Cell>::try_from(part)?;
+		let grid = ItemGrid::<Cell>::from(part);
 		if let Some(max_height) = max_height {
 			if grid.height() != max_height { bail!("Invalid part") };
 		} else {
@@ -54,13 +55,18 @@ pub fn main(input: String) -> Result<String> {
 			keys.push(Key(heights));
 		}
 	}
+	Ok((locks, keys, max_height))
+}
 
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let (locks, keys, max_height) = parsing(|| parse(&input))?;
 	let Some(max_height) = max_height else { bail!("No parts checked") };
 	let max_height = max_height - 2;
-	println!("Max height: {max_height}");
+	debug!("Max height: {max_height}");
 	let mut matches = 0;
 
-	init_progress_bar_with_eta(keys.len() * locks.len());
+	let show_progress = log::enabled(Level::Debug);
+	if show_progress { init_progress_bar_with_eta(keys.len() * locks.len()) };
 
 	for key in keys.iter() {
 		for lock in locks.iter() {
@@ -71,20 +77,22 @@ pub fn main(input: String) -> Result<String> {
 					break;
 				}
 			}
-			if valid {
-				print_progress_bar_info("Valid", &format!("{key:?} vs. {lock:?}"), Color::Green, Style::Bold);
-			} else {
-				print_progress_bar_info("Invalid", &format!("{key:?} vs. {lock:?}"), Color::Red, Style::Bold);
+			if show_progress {
+				if valid {
+					print_progress_bar_info("Valid", &format!("{key:?} vs. {lock:?}"), Color::Green, Style::Bold);
+				} else {
+					print_progress_bar_info("Invalid", &format!("{key:?} vs. {lock:?}"), Color::Red, Style::Bold);
+				}
+				inc_progress_bar();
 			}
-			inc_progress_bar();
 			if valid {
 				matches += 1;
 			}
 		}
 	}
 
-	finalize_progress_bar();
-	println!("{} keys vs {} locks", keys.len(), locks.len());
+	if show_progress { finalize_progress_bar() };
+	debug!("{} keys vs {} locks", keys.len(), locks.len());
 
 	Ok(format!("{matches}"))
 }
@@ -37,9 +37,16 @@ struct Parser {
 	}
 }
 
-pub fn main(input: String) -> Result<String> {
-	let mut enabled = true;
-	let mut sum = 0;
+///The parts of the corrupted memory that mean anything.
+enum Instruction {
+	Mul(u32, u32),
+	Do,
+	Dont,
+}
+
+///Picks every valid `mul(X,Y)`, `do()` and `don't()` out of the corrupted memory.
+fn parse(input: String) -> Vec<Instruction> {
+	let mut instructions = vec![];
 	let mut parser = Parser { input, recall_stack: vec![], i: 0 };
 
 	'try_parse_fn: while parser.i < parser.input.len() {
@@ -53,18 +60,43 @@ pub fn main(input: String) -> Result<String> {
 			let Some((left, right)) = following.split_once(',') else { parser.recall(); parser.next(); continue };
 			let Ok(left) = left.parse::<u32>() else { parser.recall(); parser.next(); continue };
 			let Ok(right) = right.parse::<u32>() else { parser.recall(); parser.next(); continue };
-			if enabled { sum += left * right };
+			instructions.push(Instruction::Mul(left, right));
 			parser.discard();
-		} else if cfg!(feature = "part2") && parser.try_match("do()") {
-			enabled = true;
+		} else if parser.try_match("do()") {
+			instructions.push(Instruction::Do);
 			parser.discard();
 			continue;
-		} else if cfg!(feature = "part2") && parser.try_match("don't()") {
-			enabled = false;
+		} else if parser.try_match("don't()") {
+			instructions.push(Instruction::Dont);
 			parser.discard();
 			continue;
 		}
 		parser.next();
 	}
-	Ok(format!("{sum}"))
+	instructions
+}
+
+///Sums the products. With `conditionals`, `do()` and `don't()` toggle whether the following
+///multiplications count.
+fn sum_products(instructions: &[Instruction], conditionals: bool) -> u32 {
+	let mut enabled = true;
+	let mut sum = 0;
+	for instruction in instructions {
+		match instruction {
+			Instruction::Mul(left, right) => if enabled { sum += left * right },
+			Instruction::Do => if conditionals { enabled = true },
+			Instruction::Dont => if conditionals { enabled = false },
+		}
+	}
+	sum
+}
+
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let instructions = parsing(|| parse(input));
+	Ok(format!("{}", sum_products(&instructions, false)))
+}
+
+pub fn part2(input: String, _: &Context) -> Result<String> {
+	let instructions = parsing(|| parse(input));
+	Ok(format!("{}", sum_products(&instructions, true)))
 }
@@ -1,8 +1,11 @@
 use crate::lib::prelude::*;
 
-pub fn main(input: String) -> Result<String> {
-	//Each rule says "X precedes Y"
-	let mut rules = Vec::<(u32, u32)>::new();
+///Says "X precedes Y".
+type Rule = (u32, u32);
+
+///Reads the ordering rules and the updates.
+fn parse(input: &str) -> Result<(Vec<Rule>, Vec<Vec<u32>>)> {
+	let mut rules = Vec::<Rule>::new();
 	let mut updates = Vec::<Vec<u32>>::new();
 
 	let mut is_parsing_updates = false;
@@ -21,26 +24,29 @@ pub fn main(input: String) -> Result<String> {
 			updates.push(update);
 		}
 	}
+	Ok((rules, updates))
+}
 
+///Sums the middle page of each correctly-ordered update, or with `reorder`, of each incorrectly-ordered
+///update after it has been fixed.
+fn sum_middle_pages(rules: &[Rule], updates: Vec<Vec<u32>>, reorder: bool) -> u32 {
 	let mut sum = 0;
 	for mut update in updates {
 		let mut valid = true;
-		'reorder_loop: loop { // < loop breaks if not explicitly continued (by part2)
+		'reorder_loop: loop { // < loop breaks if not explicitly continued (when reordering)
 			'check_pages: for i in 0..update.len() {
-				for rule in &rules {
-					if rule.1 == update[i] && let Some(j) = update.iter().position(|n| *n == rule.0) {
-						if i < j {
-							if cfg!(feature = "part2") {
-								valid = false;
-								println!("{}|{} broken", rule.0, rule.1);
-								let el = update.remove(j); // Move J so it precedes I
-								update.insert(i, el);
-								continue 'reorder_loop;
-							} else {
-								valid = false;
-								println!("{}|{} broken", rule.0, rule.1);
-								break 'check_pages;
-							}
+				for rule in rules {
+					if rule.1 == update[i] && let Some(j) = update.iter().position(|n| *n == rule.0) && i < j {
+						if reorder {
+							valid = false;
+							debug!("{}|{} broken", rule.0, rule.1);
+							let el = update.remove(j); // Move J so it precedes I
+							update.insert(i, el);
+							continue 'reorder_loop;
+						} else {
+							valid = false;
+							debug!("{}|{} broken", rule.0, rule.1);
+							break 'check_pages;
 						}
 					}
 				}
@@ -48,9 +54,19 @@ pub fn main(input: String) -> Result<String> {
 			break;
 		}
 
-		if valid ^ cfg!(feature = "part2") { // Valid updates in part 1; invalid ones in part 2
+		if valid ^ reorder { // Valid updates in part 1; invalid ones in part 2
 			sum += update[update.len() / 2];
 		}
 	}
-	Ok(format!("{sum}"))
+	sum
+}
+
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let (rules, updates) = parsing(|| parse(&input))?;
+	Ok(format!("{}", sum_middle_pages(&rules, updates, false)))
+}
+
+pub fn part2(input: String, _: &Context) -> Result<String> {
+	let (rules, updates) = parsing(|| parse(&input))?;
+	Ok(format!("{}", sum_middle_pages(&rules, updates, true)))
 }
@@ -1,5 +1,3 @@
-use std::fmt::Display;
-
 use crate::lib::prelude::*;
 
 
@@ -43,7 +41,8 @@ fn simulate_step(guard_dir: &mut Direction, guard_pos: &mut (usize, usize), grid
 	}
 }
 
-pub fn main(input: String) -> Result<String> {
+///Reads the map, returning it along with the guard's starting direction and position.
+fn parse(input: &str) -> (ItemGrid<Cell>, Direction, (usize, usize)) {
 	let mut grid = ItemGrid::<Cell>::new();
 	for line in input.lines() {
 		let mut row = vec![];
@@ -65,69 +64,63 @@ pub fn main(input: String) -> Result<String> {
 		matches!(cell, Cell::GuardStart(_, _))
 	}) else { panic!("Guard not found") };
 
-	let original_guard_dir = guard_dir.clone();
-	let original_guard_pos = (guard_x, guard_y);
-	let original_grid = grid;
+	let guard_dir = *guard_dir;
+	(grid, guard_dir, (guard_x, guard_y))
+}
 
-	if !cfg!(feature = "part2") {
-		let mut grid = original_grid;
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let (mut grid, mut guard_dir, mut guard_pos) = parsing(|| parse(&input));
+	loop {
+		if simulate_step(&mut guard_dir, &mut guard_pos, &mut grid) == State::OutOfBounds { break }
+	}
+	let mut sum = 0;
+	for (_, _, cell) in grid.iter() {
+		match cell {
+			Cell::Clear(d) => if !d.is_empty() { sum += 1 },
+			Cell::GuardStart(_, d) => if !d.is_empty() { sum += 1 },
+			Cell::Obstacle => {}
+		}
+	}
+	Ok(format!("{sum}"))
+}
+
+pub fn part2(input: String, _: &Context) -> Result<String> {
+	let (original_grid, original_guard_dir, original_guard_pos) = parsing(|| parse(&input));
+	let regular_path = {
+		let mut grid = original_grid.clone();
 		let mut guard_dir = original_guard_dir;
 		let mut guard_pos = original_guard_pos;
 		loop {
-			match simulate_step(&mut guard_dir, &mut guard_pos, &mut grid) {
-				State::OutOfBounds => break,
-				_ => {}
-			}
+			if simulate_step(&mut guard_dir, &mut guard_pos, &mut grid) == State::OutOfBounds { break }
 		}
-		let mut sum = 0;
-		for (_, _, cell) in grid.iter() {
-			match cell {
-				Cell::Clear(d) => if !d.is_empty() { sum += 1 },
-				Cell::GuardStart(_, d) => if !d.is_empty() { sum += 1 },
-				Cell::Obstacle => {}
-			}
-		}
-		Ok(format!("{sum}"))
-	} else {
-		let regular_path = {
-			let mut grid = original_grid.clone();
-			let mut guard_dir = original_guard_dir;
-			let mut guard_pos = original_guard_pos;
-			loop {
-				match simulate_step(&mut guard_dir, &mut guard_pos, &mut grid) {
-					State::OutOfBounds => break,
-					_ => {}
-				}
-			}
 
-			grid
-		};
+		grid
+	};
 
-		let mut sum = 0;
-		'find_loops: for (_, x, y) in regular_path.find_each(|c,_,_| match c {
-			Cell::Clear(d) => !d.is_empty(),
-			Cell::GuardStart(_, d) => !d.is_empty(),
-			Cell::Obstacle => false
-		}) {
-			let mut grid = original_grid.clone();
-			let mut guard_dir = original_guard_dir;
-			let mut guard_pos = original_guard_pos;
+	let mut sum = 0;
+	'find_loops: for (_, x, y) in regular_path.find_each(|c,_,_| match c {
+		Cell::Clear(d) => !d.is_empty(),
+		Cell::GuardStart(_, d) => !d.is_empty(),
+		Cell::Obstacle => false
+	}) {
+		let mut grid = original_grid.clone();
+		let mut guard_dir = original_guard_dir;
+		let mut guard_pos = original_guard_pos;
 
-			grid.set(x, y, Cell::Obstacle);
+		grid.set(x, y, Cell::Obstacle);
 
-			loop {
-				match simulate_step(&mut guard_dir, &mut guard_pos, &mut grid) {
-					State::Looping => {
-						sum += 1;
-						println!("{sum}");
-						continue 'find_loops;
-					},
-					State::OutOfBounds => break,
-					_ => {}
-				}
+		loop {
+			match simulate_step(&mut guard_dir, &mut guard_pos, &mut grid) {
+				State::Looping => {
+					sum += 1;
+					trace!("{sum}");
+					continue 'find_loops;
+				},
+				State::OutOfBounds => break,
+				_ => {}
 			}
 		}
-
-		Ok(format!("{sum}"))
 	}
+
+	Ok(format!("{sum}"))
 }
@@ -7,34 +7,39 @@ enum Operation {
 	Add,
 	#[strum(to_string = "*")]
 	Multiply,
-	#[cfg(feature = "part2")]
 	#[strum(to_string = "||")]
 	Concatenate,
 }
 
-pub fn main(input: String) -> Result<String> {
-	let mut answer = 0;
-	let lines = input.lines().count();
-	init_progress_bar_with_eta(lines);
-
-	for line in input.lines() {
+///Reads each equation's target and the numbers it's made from.
+fn parse(input: &str) -> Vec<(u64, Vec<u64>)> {
+	input.lines().map(|line| {
 		let Some((target, rhs)) = line.split_once(':') else { panic!("{line} is malformed") };
 		let target: u64 = target.parse().unwrap();
-		let parts: Vec<u64> = rhs.trim().split_whitespace().map(|v| v.parse().unwrap()).collect();
+		let parts: Vec<u64> = rhs.split_whitespace().map(|v| v.parse().unwrap()).collect();
+		(target, parts)
+	}).collect()
+}
+
+///Sums the targets of every equation that can be made true using only `operations`.
+fn calibrate(equations: &[(u64, Vec<u64>)], operations: &[Operation]) -> u64 {
+	let mut answer = 0;
+	let show_progress = log::enabled(Level::Debug);
+	if show_progress { init_progress_bar_with_eta(equations.len()) };
 
+	for &(target, ref parts) in equations {
 		let mut state = vec![0; parts.len() - 1];
 		let mut solvable = false;
 		'test_states: loop {
 			//Test the validity of this state
 			let mut sum = parts[0];
 			for i in 0..state.len() {
-				let operation = Operation::VARIANTS[state[i]];
+				let operation = operations[state[i]];
 				let num = parts[i+1];
 
 				match operation {
 					Operation::Add => sum += num,
 					Operation::Multiply => sum *= num,
-					#[cfg(feature = "part2")]
 					Operation::Concatenate => {
 						sum = format!("{sum}{num}").parse().unwrap();
 					},
@@ -42,36 +47,37 @@ pub fn main(input: String) -> Result<String> {
 			}
 			if sum == target {
 				solvable = true;
-				print_progress_bar_info(
+				if show_progress { print_progress_bar_info(
 					"Found",
 					&format!("{target}: {}{}", parts[0], parts
 						.iter()
 						.skip(1)
 						.enumerate()
-						.map(|(i,p)| format!(" {} {p}", Operation::VARIANTS[state[i]]))
+						.map(|(i,p)| format!(" {} {p}", operations[state[i]]))
 						.collect::<Vec<_>>()
 						.join(" ")),
 					Color::Green,
 					Style::Bold
-				);
-				inc_progress_bar();
+				); inc_progress_bar() };
 				break
 			};
 
 			//Increment the state
 			state[0] += 1;
 			for i in 0..state.len() {
-				if state[i] >= Operation::VARIANTS.len() {
+				if state[i] >= operations.len() {
 					state[i] = 0;
 					if i != state.len()-1 { state[i+1] += 1 }
 					else {
-						print_progress_bar_info(
-							"Failed",
-							&format!("{target}: {}", parts.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(" ")),
-							Color::Red,
-							Style::Bold
-						);
-						inc_progress_bar();
+						if show_progress {
+							print_progress_bar_info(
+								"Failed",
+								&format!("{target}: {}", parts.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(" ")),
+								Color::Red,
+								Style::Bold
+							);
+							inc_progress_bar();
+						}
 						break 'test_states
 					}
 				}
@@ -79,7 +85,17 @@ pub fn main(input: String) -> Result<String> {
 		}
 		if solvable { answer += target };
 	}
-	finalize_progress_bar();
+	if show_progress { finalize_progress_bar() };
+
+	answer
+}
+
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let equations = parsing(|| parse(&input));
+	Ok(format!("{}", calibrate(&equations, &[Operation::Add, Operation::Multiply])))
+}
 
-	Ok(format!("{answer}"))
+pub fn part2(input: String, _: &Context) -> Result<String> {
+	let equations = parsing(|| parse(&input));
+	Ok(format!("{}", calibrate(&equations, Operation::VARIANTS)))
 }
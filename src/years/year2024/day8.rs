@@ -19,18 +19,15 @@ enum Cell {
 				}
 				if d.is_empty() {
 					write!(f, "\x1b[2m.\x1b[22m")?;
-				} else {
-					for (_, dirs) in d {
-						if dirs.horizontal.is_empty() && dirs.vertical.is_empty() {
-							write!(f, "\x1b[2m.\x1b[22m")?;
-						} else if dirs.horizontal.is_empty() && !dirs.vertical.is_empty() {
-							write!(f, "|")?;
-						} else if !dirs.horizontal.is_empty() && dirs.vertical.is_empty() {
-							write!(f, "-")?;
-						} else {
-							write!(f, "+")?;
-						}
-						break;
+				} else if let Some(dirs) = d.values().next() {
+					if dirs.horizontal.is_empty() && dirs.vertical.is_empty() {
+						write!(f, "\x1b[2m.\x1b[22m")?;
+					} else if dirs.horizontal.is_empty() && !dirs.vertical.is_empty() {
+						write!(f, "|")?;
+					} else if !dirs.horizontal.is_empty() && dirs.vertical.is_empty() {
+						write!(f, "-")?;
+					} else {
+						write!(f, "+")?;
 					}
 				}
 
@@ -52,12 +49,12 @@ impl From<char> for Cell {
 	}
 }
 
-pub fn main(input: String) -> Result<String> {
-	let mut grid = ItemGrid::<Cell>::try_from(input.as_str())?;
+pub fn part1(input: String, _: &Context) -> Result<String> {
+	let mut grid = parsing(|| ItemGrid::<Cell>::from(input.as_str()));
 	for x in 0..grid.width() {
 		for y in 0..grid.height() {
 			let Cell::Antenna(ch, _) = grid.get(x, y) else { continue };
-			let ch = ch.clone();
+			let ch = *ch;
 
 			for other_x in 0..grid.width() {
 				match grid.get_mut(other_x, y) {
@@ -100,7 +97,7 @@ pub fn main(input: String) -> Result<String> {
 		'check_cell: for y in 0..grid.height() {
 			match grid.get(x, y) {
 				Cell::Clear(d) | Cell::Antenna(_, d) => {
-					for (_, group) in d {
+					for group in d.values() {
 						for h in &group.horizontal {
 							for v in &group.vertical {
 								if *h == v * 2 || *v == h * 2 {
@@ -114,7 +111,7 @@ pub fn main(input: String) -> Result<String> {
 			}
 		}
 	}
-	println!("{grid}");
+	debug!("{grid}");
 
 	Ok(format!("{sum}"))
 }